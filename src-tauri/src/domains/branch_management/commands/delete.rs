@@ -1,6 +1,13 @@
 use std::path::Path;
 
-use super::super::services::deletion::DeletedBranchInfo;
+use super::super::git::branch::BranchListOptions;
+use super::super::git::merge::MergeDetection;
+use super::super::git::plan::BranchDeletionPlan;
+use super::super::services::cleanup::{CleanupPlan, RepositoryCleanupResult};
+use super::super::services::deletion::{
+    BranchDeletionResult, DeleteMode, DeleteOptions, DeletedBranchInfo,
+};
+use super::super::services::protection::ProtectionRules;
 use crate::shared::error::AppError;
 use serde::{Deserialize, Serialize};
 
@@ -9,31 +16,130 @@ use serde::{Deserialize, Serialize};
 pub struct DeleteBranchesInput {
     pub path: String,
     pub branches: Vec<String>,
+    /// Whether unmerged branches are refused, `Force` when `None`
+    pub mode: Option<DeleteMode>,
+    /// Delete all branches or none of them, `false` when `None`
+    pub atomic: Option<bool>,
+    /// Branches to keep, only the current and default branch when `None`
+    pub protection: Option<ProtectionRules>,
+    /// Base branch `Safe` mode measures merged status against, auto-detected when `None`
+    pub base_branch: Option<String>,
+    /// Merge detection mode the listing used, `Ancestry` when `None`. `Safe` mode
+    /// still refuses branches that are only squashed or rebased into the base
+    /// branch
+    pub merge_detection: Option<MergeDetection>,
 }
 
 #[derive(Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteBranchesOutput {
     pub deleted_branches: Vec<DeletedBranchInfo>,
+    /// Outcome of each requested branch, including the ones left in place
+    pub results: Vec<BranchDeletionResult>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanDeleteBranchesOutput {
+    pub branches: Vec<BranchDeletionPlan>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteBranchesInRepositoriesInput {
+    pub paths: Vec<String>,
+    pub plan: CleanupPlan,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteBranchesInRepositoriesOutput {
+    pub results: Vec<RepositoryCleanupResult>,
 }
 
 /// Deletes branches from a git repository.
 ///
+/// A branch that cannot be deleted is reported in its result and the others
+/// are still deleted, unless the deletion is atomic: then nothing is deleted
+/// and the first failure is returned.
+///
 /// # Arguments
 ///
-/// * `input` - Input parameters containing path and branch names to delete
+/// * `input` - Input parameters containing path, branch names to delete, delete mode, atomicity, protection rules and base branch
 ///
 /// # Returns
 ///
-/// * `Result<DeleteBranchesOutput, AppError>` - The deleted branches or an error
+/// * `Result<DeleteBranchesOutput, AppError>` - The outcome of each branch or an error
 #[tauri::command(async)]
 #[specta::specta]
 pub async fn delete_branches(input: DeleteBranchesInput) -> Result<DeleteBranchesOutput, AppError> {
     let raw_path = Path::new(&input.path);
-    let deleted_branch_infos: Vec<DeletedBranchInfo> =
-        super::super::services::deletion::delete_branches(raw_path, &input.branches)?;
+    let report = super::super::services::deletion::delete_branches(
+        raw_path,
+        &input.branches,
+        &to_delete_options(&input),
+        &input.protection.unwrap_or_default(),
+    )?;
 
     Ok(DeleteBranchesOutput {
-        deleted_branches: deleted_branch_infos,
+        deleted_branches: report.deleted_branches,
+        results: report.results,
     })
 }
+
+/// Previews the deletion of branches without touching any reference.
+///
+/// # Arguments
+///
+/// * `input` - The same input as `delete_branches`
+///
+/// # Returns
+///
+/// * `Result<PlanDeleteBranchesOutput, AppError>` - Whether each branch would be deleted, and the commits it would leave unreachable
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn plan_delete_branches(
+    input: DeleteBranchesInput,
+) -> Result<PlanDeleteBranchesOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    let branches = super::super::services::deletion::plan_delete_branches(
+        raw_path,
+        &input.branches,
+        &to_delete_options(&input),
+        &input.protection.unwrap_or_default(),
+    )?;
+
+    Ok(PlanDeleteBranchesOutput { branches })
+}
+
+fn to_delete_options(input: &DeleteBranchesInput) -> DeleteOptions {
+    DeleteOptions {
+        mode: input.mode.unwrap_or_default(),
+        atomic: input.atomic.unwrap_or(false),
+        branch_list_options: BranchListOptions {
+            base_branch: input.base_branch.clone(),
+            merge_detection: input.merge_detection.unwrap_or_default(),
+        },
+    }
+}
+
+/// Deletes the branches selected by one cleanup plan in several repositories.
+///
+/// A repository that fails is reported in its result, the others are still cleaned.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing the repository paths and the cleanup plan
+///
+/// # Returns
+///
+/// * `Result<DeleteBranchesInRepositoriesOutput, AppError>` - The result of each repository
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn delete_branches_in_repositories(
+    input: DeleteBranchesInRepositoriesInput,
+) -> Result<DeleteBranchesInRepositoriesOutput, AppError> {
+    let results = super::super::services::cleanup::cleanup_repositories(&input.paths, &input.plan);
+
+    Ok(DeleteBranchesInRepositoriesOutput { results })
+}
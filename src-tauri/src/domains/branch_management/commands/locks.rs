@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use crate::shared::error::AppError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LockBranchInput {
+    pub path: String,
+    pub branch: String,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LockBranchOutput {
+    pub branch: String,
+    pub locked: bool,
+}
+
+/// Locks a branch in the repository's local config, protecting it from deletion.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing path and branch name
+///
+/// # Returns
+///
+/// * `Result<LockBranchOutput, AppError>` - The locked branch or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn lock_branch(input: LockBranchInput) -> Result<LockBranchOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    super::super::services::locking::set_branch_locked(raw_path, &input.branch, true)?;
+
+    Ok(LockBranchOutput {
+        branch: input.branch,
+        locked: true,
+    })
+}
+
+/// Removes the lock of a branch from the repository's local config.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing path and branch name
+///
+/// # Returns
+///
+/// * `Result<LockBranchOutput, AppError>` - The unlocked branch or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn unlock_branch(input: LockBranchInput) -> Result<LockBranchOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    super::super::services::locking::set_branch_locked(raw_path, &input.branch, false)?;
+
+    Ok(LockBranchOutput {
+        branch: input.branch,
+        locked: false,
+    })
+}
@@ -1,8 +1,15 @@
 mod actions;
 mod delete;
+mod locks;
 mod queries;
+mod worktrees;
 
 // Only export the functions, not the module names
 pub use actions::{restore_branch, restore_branches, switch_branch};
-pub use delete::delete_branches;
-pub use queries::is_commit_reachable;
+pub use delete::{delete_branches, delete_branches_in_repositories, plan_delete_branches};
+pub use locks::{lock_branch, unlock_branch};
+pub use queries::{
+    get_commit_details, is_commit_reachable, list_branch_diff_stats, list_gone_branches,
+    list_remote_branches,
+};
+pub use worktrees::{delete_worktree, list_worktrees, prune_worktrees};
@@ -1,5 +1,10 @@
 use std::path::Path;
 
+use super::super::git::branch::{Branch, BranchListOptions};
+use super::super::git::commit::CommitDetails;
+use super::super::git::diff::BranchDiffStats;
+use super::super::git::merge::MergeDetection;
+use super::super::services::protection::{BranchProtection, ProtectionRules};
 use crate::shared::error::AppError;
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +21,61 @@ pub struct IsCommitReachableOutput {
     pub is_reachable: bool,
 }
 
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCommitDetailsInput {
+    pub path: String,
+    pub commit_sha: String,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCommitDetailsOutput {
+    pub commit: CommitDetails,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRemoteBranchesInput {
+    pub path: String,
+    pub base_branch: Option<String>,
+    pub merge_detection: Option<MergeDetection>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRemoteBranchesOutput {
+    pub branches: Vec<Branch>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListGoneBranchesInput {
+    pub path: String,
+    pub base_branch: Option<String>,
+    pub merge_detection: Option<MergeDetection>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListGoneBranchesOutput {
+    pub branches: Vec<Branch>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBranchDiffStatsInput {
+    pub path: String,
+    pub branches: Vec<String>,
+    pub base_branch: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBranchDiffStatsOutput {
+    pub stats: Vec<BranchDiffStats>,
+}
+
 /// Checks if a commit SHA is reachable in a git repository.
 ///
 /// # Arguments
@@ -35,3 +95,98 @@ pub async fn is_commit_reachable(
 
     Ok(IsCommitReachableOutput { is_reachable })
 }
+
+/// Gets the full metadata of a commit: message body, committer, parents and trailers.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing path and commit SHA
+///
+/// # Returns
+///
+/// * `Result<GetCommitDetailsOutput, AppError>` - The commit details or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn get_commit_details(
+    input: GetCommitDetailsInput,
+) -> Result<GetCommitDetailsOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    let commit = super::super::git::commit::get_commit_details(raw_path, &input.commit_sha)?;
+
+    Ok(GetCommitDetailsOutput { commit })
+}
+
+/// Lists the remote-tracking branches of a git repository.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing the repository path and listing options
+///
+/// # Returns
+///
+/// * `Result<ListRemoteBranchesOutput, AppError>` - The remote-tracking branches or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn list_remote_branches(
+    input: ListRemoteBranchesInput,
+) -> Result<ListRemoteBranchesOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    let options = BranchListOptions {
+        base_branch: input.base_branch,
+        merge_detection: input.merge_detection.unwrap_or_default(),
+    };
+    let branches =
+        super::super::git::branch::get_all_remote_branches_with_last_commit(raw_path, &options)?;
+
+    Ok(ListRemoteBranchesOutput { branches })
+}
+
+/// Lists the local branches whose upstream no longer exists.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing the repository path and listing options
+///
+/// # Returns
+///
+/// * `Result<ListGoneBranchesOutput, AppError>` - The branches with a gone upstream or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn list_gone_branches(
+    input: ListGoneBranchesInput,
+) -> Result<ListGoneBranchesOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    let options = BranchListOptions {
+        base_branch: input.base_branch,
+        merge_detection: input.merge_detection.unwrap_or_default(),
+    };
+    let mut branches = super::super::git::branch::get_gone_branches(raw_path, &options)?;
+    BranchProtection::load(raw_path, ProtectionRules::default())?.apply(&mut branches);
+
+    Ok(ListGoneBranchesOutput { branches })
+}
+
+/// Lists the files changed, insertions and deletions of branches since their
+/// merge-base with the base branch.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing the repository path, branch names and base branch
+///
+/// # Returns
+///
+/// * `Result<ListBranchDiffStatsOutput, AppError>` - The diff statistics per branch or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn list_branch_diff_stats(
+    input: ListBranchDiffStatsInput,
+) -> Result<ListBranchDiffStatsOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    let stats = super::super::git::diff::get_branch_diff_stats(
+        raw_path,
+        &input.branches,
+        input.base_branch,
+    )?;
+
+    Ok(ListBranchDiffStatsOutput { stats })
+}
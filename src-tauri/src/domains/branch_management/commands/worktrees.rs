@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use super::super::git::worktree::{WorktreeInfo, WorktreePruneResult};
+use crate::shared::error::AppError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorktreesInput {
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorktreesOutput {
+    pub worktrees: Vec<WorktreeInfo>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteWorktreeInput {
+    pub path: String,
+    /// Name of the worktree, as returned by `list_worktrees`
+    pub name: String,
+    /// Also remove locked worktrees and worktrees with uncommitted changes
+    pub force: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteWorktreeOutput {
+    pub deleted_worktree: WorktreeInfo,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneWorktreesInput {
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneWorktreesOutput {
+    pub pruned_worktrees: Vec<WorktreeInfo>,
+    /// Outcome for each prunable worktree, failures included
+    pub results: Vec<WorktreePruneResult>,
+}
+
+/// Lists the linked worktrees of a git repository.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing the repository path
+///
+/// # Returns
+///
+/// * `Result<ListWorktreesOutput, AppError>` - The worktrees or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn list_worktrees(input: ListWorktreesInput) -> Result<ListWorktreesOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    let worktrees = super::super::git::worktree::list_worktrees(raw_path)?;
+
+    Ok(ListWorktreesOutput { worktrees })
+}
+
+/// Deletes a linked worktree, its working directory included.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing path, worktree name and force flag
+///
+/// # Returns
+///
+/// * `Result<DeleteWorktreeOutput, AppError>` - The deleted worktree or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn delete_worktree(input: DeleteWorktreeInput) -> Result<DeleteWorktreeOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    let deleted_worktree = super::super::git::worktree::remove_worktree(
+        raw_path,
+        &input.name,
+        input.force.unwrap_or(false),
+    )?;
+
+    Ok(DeleteWorktreeOutput { deleted_worktree })
+}
+
+/// Prunes the metadata of worktrees whose directory no longer exists.
+///
+/// A worktree that fails to prune does not stop the others, its error is
+/// reported in `results`.
+///
+/// # Arguments
+///
+/// * `input` - Input parameters containing the repository path
+///
+/// # Returns
+///
+/// * `Result<PruneWorktreesOutput, AppError>` - The pruned worktrees and the outcome for each, or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn prune_worktrees(input: PruneWorktreesInput) -> Result<PruneWorktreesOutput, AppError> {
+    let raw_path = Path::new(&input.path);
+    let report = super::super::git::worktree::prune_worktrees(raw_path)?;
+
+    Ok(PruneWorktreesOutput {
+        pruned_worktrees: report.pruned_worktrees,
+        results: report.results,
+    })
+}
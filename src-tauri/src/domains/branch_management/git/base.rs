@@ -0,0 +1,360 @@
+use git2::{Oid, ReferenceType, Repository};
+use std::path::Path;
+
+use crate::shared::error::AppError;
+
+/// Branch that merged status is measured against.
+#[derive(Debug, Clone)]
+pub struct BaseBranch {
+    pub name: String,
+    pub oid: Oid,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BaseBranches {
+    /// The base branch in use, `None` when nothing could be detected
+    pub selected: Option<String>,
+    /// Local branch of the selected base, e.g. `main` when `origin/main` is
+    /// selected, `None` when it has no local counterpart
+    pub selected_local: Option<String>,
+    /// Every base branch detected in the repository, in order of preference
+    pub candidates: Vec<String>,
+}
+
+/// Where a branch stands relative to the base branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaseDivergence {
+    /// Commits on the branch that are not reachable from the base
+    pub unique_commits: u32,
+    /// Best common ancestor of the branch and the base, `None` for unrelated histories
+    pub merge_base: Option<Oid>,
+}
+
+/// Detects the branches that could serve as base, in order of preference:
+/// the target of `origin/HEAD`, `init.defaultBranch`, then `main` and `master`.
+///
+/// Only branches that exist in the repository are returned.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+///
+/// # Returns
+///
+/// * `Vec<String>` - The detected base branch names
+pub fn detect_base_candidates(repo: &Repository) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+
+    if let Ok(origin_head) = repo.find_reference("refs/remotes/origin/HEAD") {
+        if origin_head.kind() == Some(ReferenceType::Symbolic) {
+            if let Some(target) = origin_head
+                .symbolic_target()
+                .and_then(|t| t.strip_prefix("refs/remotes/"))
+            {
+                names.push(target.to_string());
+            }
+        }
+    }
+
+    if let Ok(default_branch) = repo
+        .config()
+        .and_then(|config| config.get_string("init.defaultBranch"))
+    {
+        names.push(default_branch);
+    }
+
+    names.push("main".to_string());
+    names.push("master".to_string());
+
+    let mut candidates: Vec<String> = Vec::new();
+    for name in names {
+        if !candidates.contains(&name) && find_base_oid(repo, &name).is_some() {
+            candidates.push(name);
+        }
+    }
+
+    candidates
+}
+
+/// Resolves the base branch to measure merged status against.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+/// * `requested` - Base branch chosen by the user, or `None` to auto-detect it
+///
+/// # Returns
+///
+/// * `Result<Option<BaseBranch>, AppError>` - The base branch, `None` if none
+///   could be detected, or an error if the requested branch does not exist
+pub fn resolve_base_branch(
+    repo: &Repository,
+    requested: Option<&str>,
+) -> Result<Option<BaseBranch>, AppError> {
+    if let Some(name) = requested {
+        let oid = find_base_oid(repo, name).ok_or_else(|| {
+            AppError::new(
+                format!("Base branch **{}** not found", name),
+                "base_branch_not_found",
+                Some(format!(
+                    "The branch '{}' does not exist locally or as a remote-tracking branch",
+                    name
+                )),
+            )
+        })?;
+
+        return Ok(Some(BaseBranch {
+            name: name.to_string(),
+            oid,
+        }));
+    }
+
+    Ok(detect_base_candidates(repo)
+        .into_iter()
+        .next()
+        .and_then(|name| find_base_oid(repo, &name).map(|oid| BaseBranch { name, oid })))
+}
+
+/// Gets the selected base branch and all base candidates of a repository.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+/// * `requested` - Base branch chosen by the user, or `None` to auto-detect it
+///
+/// # Returns
+///
+/// * `Result<BaseBranches, AppError>` - The selected base branch and candidates or an error
+pub fn get_base_branches(path: &Path, requested: Option<&str>) -> Result<BaseBranches, AppError> {
+    let repo = Repository::open(path).map_err(|e| {
+        AppError::new(
+            format!("Failed to open git repository at {}: {}", path.display(), e),
+            "repository_open_failed",
+            Some(e.to_string()),
+        )
+    })?;
+
+    let selected = resolve_base_branch(&repo, requested)?.map(|base| base.name);
+    let selected_local = selected
+        .as_deref()
+        .and_then(|name| get_local_branch_name(&repo, name));
+
+    Ok(BaseBranches {
+        selected,
+        selected_local,
+        candidates: detect_base_candidates(&repo),
+    })
+}
+
+/// Resolves a base branch name to the local branch it stands for: the branch
+/// itself when it is local, or the local branch of the same name as a
+/// remote-tracking branch, e.g. `main` for `origin/main`.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+/// * `name` - Name of a local or remote-tracking branch
+///
+/// # Returns
+///
+/// * `Option<String>` - The local branch name, `None` when no such local branch exists
+pub fn get_local_branch_name(repo: &Repository, name: &str) -> Option<String> {
+    let local_exists = |name: &str| repo.find_reference(&format!("refs/heads/{}", name)).is_ok();
+
+    if local_exists(name) {
+        return Some(name.to_string());
+    }
+
+    let remotes = repo.remotes().ok()?;
+    remotes
+        .iter()
+        .flatten()
+        .filter_map(|remote| name.strip_prefix(&format!("{}/", remote)))
+        .find(|local_name| local_exists(local_name))
+        .map(|local_name| local_name.to_string())
+}
+
+/// Counts the commits of a branch that are not reachable from the base, which
+/// is the work that would be lost if the branch was deleted.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+/// * `branch_oid` - Commit the branch points to
+/// * `base_oid` - Commit the base branch points to, `None` when there is no
+///   base and every commit of the branch is unique
+///
+/// # Returns
+///
+/// * `Result<BaseDivergence, AppError>` - The unique commits and merge-base or an error
+pub fn get_base_divergence(
+    repo: &Repository,
+    branch_oid: Oid,
+    base_oid: Option<Oid>,
+) -> Result<BaseDivergence, AppError> {
+    let merge_base = base_oid.and_then(|base_oid| repo.merge_base(branch_oid, base_oid).ok());
+
+    let mut revwalk = repo.revwalk().map_err(map_divergence_error)?;
+    revwalk.push(branch_oid).map_err(map_divergence_error)?;
+    if let Some(base_oid) = base_oid {
+        revwalk.hide(base_oid).map_err(map_divergence_error)?;
+    }
+
+    let mut unique_commits = 0;
+    for oid in revwalk {
+        oid.map_err(map_divergence_error)?;
+        unique_commits += 1;
+    }
+
+    Ok(BaseDivergence {
+        unique_commits,
+        merge_base,
+    })
+}
+
+fn map_divergence_error(e: git2::Error) -> AppError {
+    AppError::new(
+        format!("Failed to compare branch with the base branch: {}", e),
+        "base_divergence_failed",
+        Some(e.to_string()),
+    )
+}
+
+fn find_base_oid(repo: &Repository, name: &str) -> Option<Oid> {
+    ["refs/heads/", "refs/remotes/"]
+        .iter()
+        .find_map(|prefix| repo.find_reference(&format!("{}{}", prefix, name)).ok())
+        .and_then(|reference| reference.peel_to_commit().ok())
+        .map(|commit| commit.id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_detect_base_candidates() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        // Keep the user's global init.defaultBranch out of the test
+        run_git(path, &["config", "init.defaultBranch", "main"]);
+
+        let repo = Repository::open(path).unwrap();
+        assert_eq!(detect_base_candidates(&repo), vec!["main".to_string()]);
+
+        run_git(path, &["branch", "master"]);
+        run_git(path, &["update-ref", "refs/remotes/origin/trunk", "HEAD"]);
+        run_git(
+            path,
+            &[
+                "symbolic-ref",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/trunk",
+            ],
+        );
+
+        assert_eq!(
+            detect_base_candidates(&repo),
+            vec![
+                "origin/trunk".to_string(),
+                "main".to_string(),
+                "master".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_base_branch() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        run_git(path, &["config", "init.defaultBranch", "main"]);
+        let repo = Repository::open(path).unwrap();
+
+        let base = resolve_base_branch(&repo, None).unwrap().unwrap();
+        assert_eq!(base.name, "main");
+
+        run_git(path, &["branch", "develop"]);
+        let base = resolve_base_branch(&repo, Some("develop"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(base.name, "develop");
+
+        let result = resolve_base_branch(&repo, Some("does-not-exist"));
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind, "base_branch_not_found");
+
+        run_git(path, &["branch", "-m", "main", "trunk"]);
+        assert!(resolve_base_branch(&repo, None).unwrap().is_none());
+
+        let base_branches = get_base_branches(path, None).unwrap();
+        assert_eq!(base_branches.selected, None);
+        assert!(base_branches.candidates.is_empty());
+    }
+
+    #[test]
+    fn test_get_local_branch_name() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        let remote_dir = tempfile::tempdir().unwrap();
+        run_git(remote_dir.path(), &["init", "--bare"]);
+        run_git(
+            path,
+            &[
+                "remote",
+                "add",
+                "origin",
+                remote_dir.path().to_str().unwrap(),
+            ],
+        );
+        run_git(path, &["push", "-u", "origin", "main"]);
+        run_git(
+            path,
+            &[
+                "symbolic-ref",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/main",
+            ],
+        );
+
+        let bases = get_base_branches(path, None).unwrap();
+        assert_eq!(bases.selected.as_deref(), Some("origin/main"));
+        assert_eq!(bases.selected_local.as_deref(), Some("main"));
+
+        let repo = Repository::open(path).unwrap();
+        assert_eq!(
+            get_local_branch_name(&repo, "main").as_deref(),
+            Some("main")
+        );
+        run_git(path, &["push", "origin", "main:only-remote"]);
+        run_git(path, &["fetch", "origin"]);
+        assert_eq!(get_local_branch_name(&repo, "origin/only-remote"), None);
+    }
+
+    #[test]
+    fn test_get_base_divergence() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+
+        let fork_point = run_git(path, &["rev-parse", "HEAD"]);
+        run_git(path, &["checkout", "-b", "feature"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Feature 1"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Feature 2"]);
+        run_git(path, &["checkout", "main"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Main work"]);
+
+        let repo = Repository::open(path).unwrap();
+        let oid = |rev: &str| Oid::from_str(&run_git(path, &["rev-parse", rev])).unwrap();
+
+        let divergence = get_base_divergence(&repo, oid("feature"), Some(oid("main"))).unwrap();
+        assert_eq!(divergence.unique_commits, 2);
+        assert_eq!(divergence.merge_base, Some(oid(&fork_point)));
+
+        let divergence = get_base_divergence(&repo, oid("main~1"), Some(oid("main"))).unwrap();
+        assert_eq!(divergence.unique_commits, 0);
+    }
+}
@@ -1,8 +1,75 @@
+use chrono::{DateTime, FixedOffset};
 use git2::Repository;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use super::branch::map_repository_open_error;
 use crate::shared::error::AppError;
 
+/// A timestamp as git records it: when, and in which timezone.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitTime {
+    /// Seconds since the Unix epoch
+    #[specta(type = f64)]
+    pub seconds: i64,
+    /// Offset from UTC in minutes, e.g. `120` for `+0200`
+    pub offset_minutes: i32,
+}
+
+impl CommitTime {
+    /// Formats the time in its own timezone like `git log` does,
+    /// e.g. `Mon Jan  1 12:00:00 2024 +0200`.
+    pub fn format(&self) -> String {
+        // Invalid offsets and out of range timestamps fall back to UTC and the epoch
+        let offset = FixedOffset::east_opt(self.offset_minutes * 60)
+            .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+        let dt = DateTime::from_timestamp(self.seconds, 0)
+            .unwrap_or_default()
+            .with_timezone(&offset);
+
+        dt.format("%a %b %e %T %Y %z").to_string()
+    }
+}
+
+/// A `Key: value` line from the end of a commit message, e.g. `Signed-off-by`.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitTrailer {
+    pub key: String,
+    pub value: String,
+}
+
+/// Everything about a commit, beyond the summary shown in branch listings.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitDetails {
+    pub sha: String,
+    pub short_sha: String,
+    /// First paragraph of the message
+    pub summary: String,
+    /// Message after the summary, trailers included, `None` if there is none
+    pub body: Option<String>,
+    pub author: String,
+    pub email: String,
+    pub authored_at: CommitTime,
+    pub committer: String,
+    pub committer_email: String,
+    pub committed_at: CommitTime,
+    /// More than one parent means a merge commit
+    pub parent_count: u32,
+    pub trailers: Vec<CommitTrailer>,
+}
+
+impl From<git2::Time> for CommitTime {
+    fn from(time: git2::Time) -> Self {
+        Self {
+            seconds: time.seconds(),
+            offset_minutes: time.offset_minutes(),
+        }
+    }
+}
+
 pub fn is_commit_reachable(path: &Path, commit_sha: &str) -> Result<bool, AppError> {
     if commit_sha.is_empty() {
         return Ok(false);
@@ -18,12 +85,140 @@ pub fn is_commit_reachable(path: &Path, commit_sha: &str) -> Result<bool, AppErr
     Ok(result)
 }
 
+/// Gets the full metadata of a commit.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+/// * `commit_sha` - Full or abbreviated SHA of the commit
+///
+/// # Returns
+///
+/// * `Result<CommitDetails, AppError>` - The commit details or an error
+pub fn get_commit_details(path: &Path, commit_sha: &str) -> Result<CommitDetails, AppError> {
+    let repo = Repository::open(path).map_err(|e| map_repository_open_error(path, e))?;
+
+    let commit = repo
+        .revparse_single(commit_sha)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|e| {
+            AppError::new(
+                format!("Commit **{}** not found", commit_sha),
+                "commit_not_found",
+                Some(e.to_string()),
+            )
+        })?;
+
+    let author = commit.author();
+    let committer = commit.committer();
+    let message = String::from_utf8_lossy(commit.message_bytes()).to_string();
+
+    // A message that cannot be parsed simply has no trailers
+    let trailers = git2::message_trailers_strs(&message)
+        .map(|trailers| {
+            trailers
+                .iter()
+                .map(|(key, value)| CommitTrailer {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let sha = commit.id().to_string();
+    let short_sha = sha[0..7].to_string();
+
+    Ok(CommitDetails {
+        sha,
+        short_sha,
+        summary: commit.summary().unwrap_or("").to_string(),
+        body: commit.body().map(|body| body.trim_end().to_string()),
+        author: author.name().unwrap_or("").to_string(),
+        email: author.email().unwrap_or("").to_string(),
+        authored_at: CommitTime::from(author.when()),
+        committer: committer.name().unwrap_or("").to_string(),
+        committer_email: committer.email().unwrap_or("").to_string(),
+        committed_at: CommitTime::from(committer.when()),
+        parent_count: commit.parent_count() as u32,
+        trailers,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::shared::utils::test_utils::{setup_test_repo, DirectoryGuard};
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
     use std::process::Command;
 
+    #[test]
+    fn test_commit_time_format() {
+        let time = CommitTime::from(git2::Time::new(1_704_110_400, 120));
+        assert_eq!(time.seconds, 1_704_110_400);
+        assert_eq!(time.offset_minutes, 120);
+        assert_eq!(time.format(), "Mon Jan  1 14:00:00 2024 +0200");
+
+        let time = CommitTime::from(git2::Time::new(0, -300));
+        assert_eq!(time.format(), "Wed Dec 31 19:00:00 1969 -0500");
+    }
+
+    #[test]
+    fn test_get_commit_details() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+
+        run_git(path, &["checkout", "-b", "feature"]);
+        run_git(
+            path,
+            &[
+                "commit",
+                "--allow-empty",
+                "-m",
+                "Add feature",
+                "-m",
+                "Explain why.",
+                "-m",
+                "Co-authored-by: Jane Doe <jane@example.com>\nSigned-off-by: Test User <test@example.com>",
+            ],
+        );
+        let sha = run_git(path, &["rev-parse", "HEAD"]);
+
+        let details = get_commit_details(path, &sha[0..10]).unwrap();
+        assert_eq!(details.sha, sha);
+        assert_eq!(details.summary, "Add feature");
+        assert!(details.body.as_deref().unwrap().starts_with("Explain why."));
+        assert_eq!(details.parent_count, 1);
+        assert_eq!(
+            details.trailers,
+            vec![
+                CommitTrailer {
+                    key: "Co-authored-by".to_string(),
+                    value: "Jane Doe <jane@example.com>".to_string(),
+                },
+                CommitTrailer {
+                    key: "Signed-off-by".to_string(),
+                    value: "Test User <test@example.com>".to_string(),
+                },
+            ]
+        );
+
+        run_git(path, &["checkout", "main"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Main work"]);
+        run_git(
+            path,
+            &["merge", "--no-ff", "feature", "-m", "Merge feature"],
+        );
+
+        let details = get_commit_details(path, "HEAD").unwrap();
+        assert_eq!(details.parent_count, 2);
+        assert_eq!(details.body, None);
+        assert!(details.trailers.is_empty());
+
+        let result = get_commit_details(path, "deadbeef");
+        assert_eq!(result.unwrap_err().kind, "commit_not_found");
+    }
+
     #[test]
     fn test_is_commit_reachable() {
         let _guard = DirectoryGuard::new();
@@ -0,0 +1,162 @@
+use git2::{BranchType, Repository};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::branch::{get_merge_target, map_repository_open_error, BranchListOptions};
+use crate::shared::error::AppError;
+
+/// Size of a branch: the changes between its merge-base with the base branch
+/// and its tip, like `git diff --shortstat base...branch`.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchDiffStats {
+    pub branch: String,
+    pub files_changed: u32,
+    pub insertions: u32,
+    pub deletions: u32,
+}
+
+/// Computes the diff statistics of branches against the base branch.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+/// * `branches` - Names of the local or remote-tracking branches
+/// * `base_branch` - Base branch chosen by the user, or `None` to auto-detect it
+///
+/// # Returns
+///
+/// * `Result<Vec<BranchDiffStats>, AppError>` - The statistics, in the order of `branches`, or an error
+pub fn get_branch_diff_stats(
+    path: &Path,
+    branches: &[String],
+    base_branch: Option<String>,
+) -> Result<Vec<BranchDiffStats>, AppError> {
+    let repo = Repository::open(path).map_err(|e| map_repository_open_error(path, e))?;
+    let options = BranchListOptions {
+        base_branch,
+        ..Default::default()
+    };
+    let merge_target = get_merge_target(&repo, &options)?;
+
+    let mut found_branches = Vec::new();
+    let mut not_found_branches: Vec<String> = Vec::new();
+    for name in branches {
+        match find_branch(&repo, name) {
+            Some(branch) => found_branches.push((name, branch)),
+            None => not_found_branches.push(name.clone()),
+        }
+    }
+
+    if !not_found_branches.is_empty() {
+        return Err(AppError::new(
+            format!(
+                "Branch(es) not found: **{}**",
+                not_found_branches.join(", ")
+            ),
+            "branches_not_found",
+            Some(format!(
+                "Cannot find the following branches: {}. Path: {}",
+                not_found_branches.join(", "),
+                path.display()
+            )),
+        ));
+    }
+
+    let mut stats = Vec::new();
+
+    for (name, branch) in found_branches {
+        let tip = branch.get().peel_to_commit().map_err(|e| {
+            AppError::new(
+                format!("Failed to get commit for branch '{}': {}", name, e),
+                "commit_not_found",
+                Some(e.to_string()),
+            )
+        })?;
+
+        // Unrelated histories, or no base at all, are measured against the empty tree
+        let merge_base = merge_target.and_then(|target| repo.merge_base(target, tip.id()).ok());
+        let base_tree = match merge_base {
+            Some(oid) => Some(
+                repo.find_commit(oid)
+                    .and_then(|commit| commit.tree())
+                    .map_err(map_diff_error)?,
+            ),
+            None => None,
+        };
+        let tip_tree = tip.tree().map_err(map_diff_error)?;
+
+        let diff_stats = repo
+            .diff_tree_to_tree(base_tree.as_ref(), Some(&tip_tree), None)
+            .and_then(|diff| diff.stats())
+            .map_err(map_diff_error)?;
+
+        stats.push(BranchDiffStats {
+            branch: name.clone(),
+            files_changed: diff_stats.files_changed() as u32,
+            insertions: diff_stats.insertions() as u32,
+            deletions: diff_stats.deletions() as u32,
+        });
+    }
+
+    Ok(stats)
+}
+
+fn find_branch<'repo>(repo: &'repo Repository, name: &str) -> Option<git2::Branch<'repo>> {
+    repo.find_branch(name, BranchType::Local)
+        .or_else(|_| repo.find_branch(name, BranchType::Remote))
+        .ok()
+}
+
+fn map_diff_error(e: git2::Error) -> AppError {
+    AppError::new(
+        format!("Failed to compute diff statistics: {}", e),
+        "diff_stats_failed",
+        Some(e.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_get_branch_diff_stats() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        run_git(path, &["config", "init.defaultBranch", "main"]);
+
+        run_git(path, &["checkout", "-b", "feature"]);
+        std::fs::write(path.join("a.txt"), "one\ntwo\nthree\n").unwrap();
+        std::fs::write(path.join("b.txt"), "b\n").unwrap();
+        run_git(path, &["add", "."]);
+        run_git(path, &["commit", "-m", "Add files"]);
+        std::fs::write(path.join("a.txt"), "one\nthree\n").unwrap();
+        run_git(path, &["commit", "-am", "Drop a line"]);
+
+        // Work on main after the fork point must not count towards the branch
+        run_git(path, &["checkout", "main"]);
+        std::fs::write(path.join("c.txt"), "c\n").unwrap();
+        run_git(path, &["add", "."]);
+        run_git(path, &["commit", "-m", "Add c"]);
+
+        let branches = vec!["feature".to_string(), "main".to_string()];
+        let stats = get_branch_diff_stats(path, &branches, None).unwrap();
+
+        assert_eq!(
+            stats[0],
+            BranchDiffStats {
+                branch: "feature".to_string(),
+                files_changed: 2,
+                insertions: 3,
+                deletions: 0,
+            }
+        );
+        assert_eq!(stats[1].files_changed, 0);
+
+        let result = get_branch_diff_stats(path, &["missing".to_string()], None);
+        assert_eq!(result.unwrap_err().kind, "branches_not_found");
+    }
+}
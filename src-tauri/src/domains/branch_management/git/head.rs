@@ -0,0 +1,111 @@
+use git2::{ReferenceType, Repository};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::branch::map_repository_open_error;
+use crate::shared::error::AppError;
+
+/// What HEAD points to.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "PascalCase")]
+pub enum CurrentBranch {
+    /// A branch is checked out
+    Branch { name: String },
+    /// A commit is checked out directly, e.g. during a bisect or on a tag
+    Detached { sha: String },
+    /// A branch is checked out but has no commit yet, e.g. after `git init`
+    Unborn { name: String },
+}
+
+impl CurrentBranch {
+    /// Name of the checked out branch, `None` when HEAD is detached.
+    pub fn branch_name(&self) -> Option<&str> {
+        match self {
+            CurrentBranch::Branch { name } | CurrentBranch::Unborn { name } => Some(name),
+            CurrentBranch::Detached { .. } => None,
+        }
+    }
+}
+
+/// Gets what HEAD of a repository points to.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+///
+/// # Returns
+///
+/// * `Result<CurrentBranch, AppError>` - The state of HEAD or an error
+pub fn get_head_state(path: &Path) -> Result<CurrentBranch, AppError> {
+    let repo = Repository::open(path).map_err(|e| map_repository_open_error(path, e))?;
+    read_head_state(&repo)
+}
+
+/// Reads what HEAD points to without failing on detached or unborn HEADs,
+/// which `Repository::head` reports as errors or as plain commits.
+pub(crate) fn read_head_state(repo: &Repository) -> Result<CurrentBranch, AppError> {
+    let head = repo.find_reference("HEAD").map_err(|e| {
+        AppError::new(
+            format!("Failed to get HEAD: {}", e),
+            "head_not_found",
+            Some(e.to_string()),
+        )
+    })?;
+
+    if head.kind() == Some(ReferenceType::Direct) {
+        let sha = head.target().map(|oid| oid.to_string()).unwrap_or_default();
+        return Ok(CurrentBranch::Detached { sha });
+    }
+
+    let target = head.symbolic_target().ok_or_else(|| {
+        AppError::new(
+            "Failed to get branch name of HEAD".to_string(),
+            "invalid_branch_name",
+            Some("Branch name contains invalid UTF-8".to_string()),
+        )
+    })?;
+    let name = target
+        .strip_prefix("refs/heads/")
+        .unwrap_or(target)
+        .to_string();
+
+    if head.resolve().is_ok() {
+        Ok(CurrentBranch::Branch { name })
+    } else {
+        Ok(CurrentBranch::Unborn { name })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_get_head_state() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+
+        assert_eq!(
+            get_head_state(path).unwrap(),
+            CurrentBranch::Branch {
+                name: "main".to_string()
+            }
+        );
+
+        let sha = run_git(path, &["rev-parse", "HEAD"]);
+        run_git(path, &["checkout", "--detach"]);
+        let state = get_head_state(path).unwrap();
+        assert_eq!(state, CurrentBranch::Detached { sha });
+        assert_eq!(state.branch_name(), None);
+
+        run_git(path, &["checkout", "--orphan", "fresh"]);
+        assert_eq!(
+            get_head_state(path).unwrap(),
+            CurrentBranch::Unborn {
+                name: "fresh".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use git2::{BranchType, Config, ConfigLevel, Repository};
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::branch::map_repository_open_error;
+use crate::shared::error::AppError;
+
+/// Local config variable marking a branch as locked, namespaced so it never
+/// clashes with git's own `branch.<name>.*` settings.
+const LOCK_VARIABLE: &str = "cutBranchesLocked";
+
+/// Locks or unlocks a local branch in the repository's local config.
+///
+/// Locks live in `branch.<name>.cutBranchesLocked`, so they travel with the
+/// repository instead of the app. Deleting the branch drops its lock too.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+/// * `branch_name` - Name of the local branch
+/// * `locked` - Whether to lock or unlock the branch
+///
+/// # Returns
+///
+/// * `Result<(), AppError>` - Nothing, or an error
+pub fn set_branch_locked(path: &Path, branch_name: &str, locked: bool) -> Result<(), AppError> {
+    let repo = Repository::open(path).map_err(|e| map_repository_open_error(path, e))?;
+
+    repo.find_branch(branch_name, BranchType::Local)
+        .map_err(|e| {
+            AppError::new(
+                format!("Branch **{}** not found", branch_name),
+                "branch_not_found",
+                Some(e.to_string()),
+            )
+        })?;
+
+    let mut config = open_local_config(&repo)?;
+    let key = lock_key(branch_name);
+    let result = if locked {
+        config.set_bool(&key, true)
+    } else {
+        match config.remove(&key) {
+            // Unlocking a branch that is not locked is a no-op
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(()),
+            result => result,
+        }
+    };
+
+    result.map_err(|e| {
+        AppError::new(
+            format!(
+                "Failed to update the lock of branch '{}': {}",
+                branch_name, e
+            ),
+            "branch_lock_failed",
+            Some(e.to_string()),
+        )
+    })
+}
+
+/// Lists the locked branches of a repository.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+///
+/// # Returns
+///
+/// * `Result<HashSet<String>, AppError>` - Names of the locked branches or an error
+pub fn list_locked_branches(path: &Path) -> Result<HashSet<String>, AppError> {
+    let repo = Repository::open(path).map_err(|e| map_repository_open_error(path, e))?;
+    Ok(get_locked_branches(&repo))
+}
+
+/// Reads which local branches are locked, treating an unreadable config as
+/// having no locks.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+///
+/// # Returns
+///
+/// * `HashSet<String>` - Names of the locked branches
+pub fn get_locked_branches(repo: &Repository) -> HashSet<String> {
+    let mut locked_branches = HashSet::new();
+
+    let config = match open_local_config(repo) {
+        Ok(config) => config,
+        Err(_) => return locked_branches,
+    };
+    let branches = match repo.branches(Some(BranchType::Local)) {
+        Ok(branches) => branches,
+        Err(_) => return locked_branches,
+    };
+
+    for (branch, _) in branches.flatten() {
+        if let Ok(Some(name)) = branch.name() {
+            if config.get_bool(&lock_key(name)).unwrap_or(false) {
+                locked_branches.insert(name.to_string());
+            }
+        }
+    }
+
+    locked_branches
+}
+
+fn lock_key(branch_name: &str) -> String {
+    format!("branch.{}.{}", branch_name, LOCK_VARIABLE)
+}
+
+fn open_local_config(repo: &Repository) -> Result<Config, AppError> {
+    repo.config()
+        .and_then(|config| config.open_level(ConfigLevel::Local))
+        .map_err(|e| {
+            AppError::new(
+                format!("Failed to open the repository config: {}", e),
+                "config_open_failed",
+                Some(e.to_string()),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::branch_management::git::branch::{
+        get_all_branches_with_last_commit, BranchListOptions,
+    };
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_lock_and_unlock_branch() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        run_git(path, &["branch", "feature"]);
+
+        set_branch_locked(path, "feature", true).unwrap();
+        assert_eq!(
+            run_git(
+                path,
+                &["config", "--local", "branch.feature.cutBranchesLocked"]
+            ),
+            "true"
+        );
+        let repo = Repository::open(path).unwrap();
+        assert_eq!(
+            get_locked_branches(&repo),
+            HashSet::from(["feature".to_string()])
+        );
+        let branches =
+            get_all_branches_with_last_commit(path, &BranchListOptions::default()).unwrap();
+        assert!(branches.iter().any(|b| b.name == "feature" && b.locked));
+        assert!(branches.iter().any(|b| b.name == "main" && !b.locked));
+
+        set_branch_locked(path, "feature", false).unwrap();
+        assert!(get_locked_branches(&repo).is_empty());
+        // Unlocking twice is fine
+        set_branch_locked(path, "feature", false).unwrap();
+
+        let result = set_branch_locked(path, "missing", true);
+        assert_eq!(result.unwrap_err().kind, "branch_not_found");
+    }
+}
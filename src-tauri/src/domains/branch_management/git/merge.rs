@@ -0,0 +1,351 @@
+use git2::{Oid, Repository};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::shared::error::AppError;
+
+/// How a branch ended up in the base branch.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum MergeKind {
+    /// The branch tip is an ancestor of the base branch
+    Ancestor,
+    /// The cumulative diff of the branch was applied to the base as one commit
+    Squashed,
+    /// Every commit of the branch was applied to the base with a new SHA
+    Rebased,
+    /// No evidence that the branch was merged
+    None,
+}
+
+/// How many of a branch's own commits already have an equivalent commit, one
+/// with the same patch-id, in the base branch. Mirrors `git cherry`.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitsInBase {
+    /// Branch commits whose patch is already in the base branch
+    pub count: u32,
+    /// Non-merge commits on the branch that are not reachable from the base
+    pub total: u32,
+}
+
+/// Result of detecting whether a branch is merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeStatus {
+    pub kind: MergeKind,
+    /// Only computed with `MergeDetection::Content` for non-ancestor branches
+    pub commits_in_base: Option<CommitsInBase>,
+}
+
+/// How much work is spent deciding whether a branch is merged.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum MergeDetection {
+    /// Only check whether the branch is an ancestor of the base branch
+    #[default]
+    Ancestry,
+    /// Also compare patch-ids against the base history to catch squash and
+    /// rebase merges
+    Content,
+}
+
+/// Detects whether branches are merged into a target commit.
+///
+/// Patch-ids of the target history are cached, so one detector should be
+/// reused for every branch of a listing.
+pub struct MergeDetector<'repo> {
+    repo: &'repo Repository,
+    /// `None` when there is nothing to merge into, e.g. on an unborn HEAD
+    /// without a base branch
+    target: Option<Oid>,
+    mode: MergeDetection,
+    patch_ids: HashMap<Oid, Option<Oid>>,
+}
+
+impl<'repo> MergeDetector<'repo> {
+    pub fn new(repo: &'repo Repository, target: Option<Oid>, mode: MergeDetection) -> Self {
+        Self {
+            repo,
+            target,
+            mode,
+            patch_ids: HashMap::new(),
+        }
+    }
+
+    /// Detects how the commit a branch points to was merged into the target.
+    ///
+    /// # Arguments
+    ///
+    /// * `branch_oid` - Commit the branch points to
+    ///
+    /// # Returns
+    ///
+    /// * `Result<MergeStatus, AppError>` - The kind of merge detected or an error
+    pub fn detect(&mut self, branch_oid: Oid) -> Result<MergeStatus, AppError> {
+        let not_merged = MergeStatus {
+            kind: MergeKind::None,
+            commits_in_base: None,
+        };
+
+        let target = match self.target {
+            Some(target) => target,
+            None => return Ok(not_merged),
+        };
+
+        // A branch pointing at the target itself is "merged" by definition
+        if branch_oid == target
+            || self
+                .repo
+                .graph_descendant_of(target, branch_oid)
+                .unwrap_or(false)
+        {
+            return Ok(MergeStatus {
+                kind: MergeKind::Ancestor,
+                commits_in_base: None,
+            });
+        }
+
+        if self.mode == MergeDetection::Ancestry {
+            return Ok(not_merged);
+        }
+
+        // Unrelated histories cannot have been squashed or rebased
+        let merge_base = match self.repo.merge_base(target, branch_oid) {
+            Ok(oid) => oid,
+            Err(_) => return Ok(not_merged),
+        };
+
+        let mut target_patch_ids = HashSet::new();
+        for oid in self.commits_between(target, merge_base)? {
+            if let Some(patch_id) = self.commit_patch_id(oid)? {
+                target_patch_ids.insert(patch_id);
+            }
+        }
+
+        let commits_in_base = self.count_commits_in_base(branch_oid, target, &target_patch_ids)?;
+
+        let kind = if self.is_squash_merged(branch_oid, merge_base, &target_patch_ids)? {
+            MergeKind::Squashed
+        } else if commits_in_base.total > 0 && commits_in_base.count == commits_in_base.total {
+            MergeKind::Rebased
+        } else {
+            MergeKind::None
+        };
+
+        Ok(MergeStatus {
+            kind,
+            commits_in_base: Some(commits_in_base),
+        })
+    }
+
+    /// Checks whether the diff between the merge-base and the branch tip, which
+    /// is what a squash merge commit contains, was applied to the target.
+    fn is_squash_merged(
+        &self,
+        branch_oid: Oid,
+        merge_base: Oid,
+        target_patch_ids: &HashSet<Oid>,
+    ) -> Result<bool, AppError> {
+        let base_tree = self
+            .repo
+            .find_commit(merge_base)
+            .and_then(|commit| commit.tree())
+            .map_err(map_merge_detection_error)?;
+        let branch_tree = self
+            .repo
+            .find_commit(branch_oid)
+            .and_then(|commit| commit.tree())
+            .map_err(map_merge_detection_error)?;
+
+        let diff = self
+            .repo
+            .diff_tree_to_tree(Some(&base_tree), Some(&branch_tree), None)
+            .map_err(map_merge_detection_error)?;
+
+        // A branch that changes nothing has nothing to look for
+        if diff.deltas().len() == 0 {
+            return Ok(false);
+        }
+
+        let squash_patch_id = diff.patchid(None).map_err(map_merge_detection_error)?;
+
+        Ok(target_patch_ids.contains(&squash_patch_id))
+    }
+
+    /// Counts the branch commits that have an equivalent commit in the target,
+    /// like the `-` lines of `git cherry <target> <branch>`.
+    fn count_commits_in_base(
+        &mut self,
+        branch_oid: Oid,
+        target: Oid,
+        target_patch_ids: &HashSet<Oid>,
+    ) -> Result<CommitsInBase, AppError> {
+        let mut commits_in_base = CommitsInBase { count: 0, total: 0 };
+
+        for oid in self.commits_between(branch_oid, target)? {
+            // Merge commits are skipped, as git cherry does
+            if let Some(patch_id) = self.commit_patch_id(oid)? {
+                commits_in_base.total += 1;
+                if target_patch_ids.contains(&patch_id) {
+                    commits_in_base.count += 1;
+                }
+            }
+        }
+
+        Ok(commits_in_base)
+    }
+
+    /// Lists the commits reachable from `from` but not from `hide`.
+    fn commits_between(&self, from: Oid, hide: Oid) -> Result<Vec<Oid>, AppError> {
+        let mut revwalk = self.repo.revwalk().map_err(map_merge_detection_error)?;
+        revwalk.push(from).map_err(map_merge_detection_error)?;
+        revwalk.hide(hide).map_err(map_merge_detection_error)?;
+
+        revwalk
+            .collect::<Result<Vec<Oid>, git2::Error>>()
+            .map_err(map_merge_detection_error)
+    }
+
+    /// Computes the patch-id of a commit against its parent, or `None` for
+    /// merge commits, whose changes do not belong to a single patch.
+    fn commit_patch_id(&mut self, oid: Oid) -> Result<Option<Oid>, AppError> {
+        if let Some(patch_id) = self.patch_ids.get(&oid) {
+            return Ok(*patch_id);
+        }
+
+        let commit = self
+            .repo
+            .find_commit(oid)
+            .map_err(map_merge_detection_error)?;
+
+        let patch_id = if commit.parent_count() > 1 {
+            None
+        } else {
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree().map_err(map_merge_detection_error)?),
+                Err(_) => None, // Root commit, diff against the empty tree
+            };
+            let tree = commit.tree().map_err(map_merge_detection_error)?;
+            let diff = self
+                .repo
+                .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+                .map_err(map_merge_detection_error)?;
+
+            Some(diff.patchid(None).map_err(map_merge_detection_error)?)
+        };
+
+        self.patch_ids.insert(oid, patch_id);
+        Ok(patch_id)
+    }
+}
+
+fn map_merge_detection_error(e: git2::Error) -> AppError {
+    AppError::new(
+        format!("Failed to detect merged status: {}", e),
+        "merge_detection_failed",
+        Some(e.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    fn rev_parse(path: &std::path::Path, rev: &str) -> Oid {
+        Oid::from_str(&run_git(path, &["rev-parse", rev])).unwrap()
+    }
+
+    #[test]
+    fn test_detect_squash_merged_branch() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+
+        run_git(path, &["checkout", "-b", "feature"]);
+        std::fs::write(path.join("a.txt"), "a").unwrap();
+        run_git(path, &["add", "a.txt"]);
+        run_git(path, &["commit", "-m", "Add a"]);
+        std::fs::write(path.join("b.txt"), "b").unwrap();
+        run_git(path, &["add", "b.txt"]);
+        run_git(path, &["commit", "-m", "Add b"]);
+
+        run_git(path, &["checkout", "-b", "unmerged", "main"]);
+        std::fs::write(path.join("c.txt"), "c").unwrap();
+        run_git(path, &["add", "c.txt"]);
+        run_git(path, &["commit", "-m", "Add c"]);
+
+        run_git(path, &["checkout", "main"]);
+        run_git(path, &["merge", "--squash", "feature"]);
+        run_git(path, &["commit", "-m", "Squashed feature"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Later work"]);
+
+        let repo = Repository::open(path).unwrap();
+        let target = rev_parse(path, "main");
+
+        let mut detector = MergeDetector::new(&repo, Some(target), MergeDetection::Content);
+        assert_eq!(
+            detector.detect(rev_parse(path, "feature")).unwrap().kind,
+            MergeKind::Squashed
+        );
+        assert_eq!(
+            detector.detect(rev_parse(path, "unmerged")).unwrap().kind,
+            MergeKind::None
+        );
+        assert_eq!(
+            detector.detect(rev_parse(path, "main~2")).unwrap().kind,
+            MergeKind::Ancestor
+        );
+
+        // Ancestry mode never pays for patch-ids
+        let mut detector = MergeDetector::new(&repo, Some(target), MergeDetection::Ancestry);
+        assert_eq!(
+            detector.detect(rev_parse(path, "feature")).unwrap().kind,
+            MergeKind::None
+        );
+    }
+
+    #[test]
+    fn test_detect_rebase_merged_branch() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+
+        for (branch, files) in [("rebased", ["a", "b"]), ("partial", ["c", "d"])] {
+            run_git(path, &["checkout", "-b", branch, "main"]);
+            for file in files {
+                std::fs::write(path.join(format!("{}.txt", file)), file).unwrap();
+                run_git(path, &["add", "."]);
+                run_git(path, &["commit", "-m", &format!("Add {}", file)]);
+            }
+        }
+
+        // Replay the commits on a diverged main so they get new SHAs, and
+        // only replay half of `partial`
+        run_git(path, &["checkout", "main"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Main work"]);
+        run_git(path, &["cherry-pick", "rebased~1", "rebased"]);
+        run_git(path, &["cherry-pick", "partial~1"]);
+
+        let repo = Repository::open(path).unwrap();
+        let mut detector = MergeDetector::new(
+            &repo,
+            Some(rev_parse(path, "main")),
+            MergeDetection::Content,
+        );
+
+        let status = detector.detect(rev_parse(path, "rebased")).unwrap();
+        assert_eq!(status.kind, MergeKind::Rebased);
+        assert_eq!(
+            status.commits_in_base,
+            Some(CommitsInBase { count: 2, total: 2 })
+        );
+
+        let status = detector.detect(rev_parse(path, "partial")).unwrap();
+        assert_eq!(status.kind, MergeKind::None);
+        assert_eq!(
+            status.commits_in_base,
+            Some(CommitsInBase { count: 1, total: 2 })
+        );
+    }
+}
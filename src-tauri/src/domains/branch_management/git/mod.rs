@@ -1,2 +1,12 @@
+pub mod base;
 pub mod branch;
-pub(crate) mod commit;
+pub mod commit;
+pub mod diff;
+pub mod head;
+pub mod lock;
+pub mod merge;
+pub mod plan;
+pub(crate) mod reachability;
+pub(crate) mod reflog;
+pub(crate) mod upstream;
+pub mod worktree;
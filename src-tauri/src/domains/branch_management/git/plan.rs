@@ -0,0 +1,205 @@
+use git2::{BranchType, Repository};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::super::services::deletion::{DeleteMode, DeleteOptions};
+use super::branch::{
+    get_branch_info, is_safe_to_delete, map_repository_open_error, BranchInfoContext,
+};
+use super::reachability::count_unreachable_commits;
+use crate::shared::error::AppError;
+
+/// What deleting a branch would do.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum DeletionAction {
+    Delete,
+    Blocked,
+}
+
+/// Why a branch cannot be deleted.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum BlockedReason {
+    /// No local branch has this name
+    NotFound,
+    /// The branch is checked out in this repository
+    Current,
+    /// The branch is checked out in another worktree
+    Worktree,
+    /// A protection rule keeps the branch
+    Protected,
+    /// The branch is not merged and would be refused in `Safe` mode
+    NotMerged,
+}
+
+/// The planned deletion of one branch.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchDeletionPlan {
+    pub branch: String,
+    pub action: DeletionAction,
+    /// Why the branch is kept, `None` when it would be deleted
+    pub blocked_reason: Option<BlockedReason>,
+    /// Worktree the branch is checked out in, if any
+    pub worktree_path: Option<String>,
+    /// Commits no other reference reaches once the planned branches are deleted
+    pub unreachable_commits: u32,
+}
+
+/// Plans the deletion of local branches without touching any reference.
+///
+/// Commits shared by several deleted branches count towards each of them.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+/// * `branches` - Names of the branches to delete
+/// * `options` - Whether and how unmerged branches are refused, atomicity does not change the plan
+/// * `protected_branches` - Names of the requested branches protection rules keep
+///
+/// # Returns
+///
+/// * `Result<Vec<BranchDeletionPlan>, AppError>` - The plan of each branch, in the order of `branches`, or an error
+pub fn plan_delete_branches(
+    path: &Path,
+    branches: &[String],
+    options: &DeleteOptions,
+    protected_branches: &[String],
+) -> Result<Vec<BranchDeletionPlan>, AppError> {
+    let repo = Repository::open(path).map_err(|e| map_repository_open_error(path, e))?;
+    let mut context = BranchInfoContext::load(&repo, &options.branch_list_options)?;
+
+    let mut plans = Vec::new();
+
+    for name in branches {
+        let worktree_path = context
+            .worktree_path(name)
+            .map(|worktree| worktree.display().to_string());
+
+        let blocked_reason = if repo.find_branch(name, BranchType::Local).is_err() {
+            Some(BlockedReason::NotFound)
+        } else if context.head_state().branch_name() == Some(name.as_str()) {
+            Some(BlockedReason::Current)
+        } else if worktree_path.is_some() {
+            Some(BlockedReason::Worktree)
+        } else if protected_branches.contains(name) {
+            Some(BlockedReason::Protected)
+        } else if options.mode == DeleteMode::Safe
+            && !is_safe_to_delete(&get_branch_info(&repo, name, &mut context)?)
+        {
+            Some(BlockedReason::NotMerged)
+        } else {
+            None
+        };
+
+        plans.push(BranchDeletionPlan {
+            branch: name.clone(),
+            action: match blocked_reason {
+                Some(_) => DeletionAction::Blocked,
+                None => DeletionAction::Delete,
+            },
+            blocked_reason,
+            worktree_path,
+            unreachable_commits: 0,
+        });
+    }
+
+    let deleted_refs: HashSet<String> = plans
+        .iter()
+        .filter(|plan| plan.action == DeletionAction::Delete)
+        .map(|plan| format!("refs/heads/{}", plan.branch))
+        .collect();
+
+    for plan in plans
+        .iter_mut()
+        .filter(|plan| plan.action == DeletionAction::Delete)
+    {
+        let tip = repo
+            .find_branch(&plan.branch, BranchType::Local)
+            .and_then(|branch| branch.get().peel_to_commit())
+            .map_err(|e| {
+                AppError::new(
+                    format!("Failed to get commit for branch '{}': {}", plan.branch, e),
+                    "commit_not_found",
+                    Some(e.to_string()),
+                )
+            })?;
+        plan.unreachable_commits = count_unreachable_commits(&repo, tip.id(), &deleted_refs)?;
+    }
+
+    Ok(plans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_plan_delete_branches() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        let worktrees_dir = tempfile::tempdir().unwrap();
+        let linked_path = worktrees_dir.path().join("linked");
+
+        run_git(path, &["branch", "merged"]);
+        run_git(path, &["checkout", "-b", "unmerged"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Work 1"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Work 2"]);
+        run_git(path, &["checkout", "main"]);
+        run_git(
+            path,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                "linked-branch",
+                linked_path.to_str().unwrap(),
+            ],
+        );
+        let before = run_git(path, &["for-each-ref"]);
+
+        let branches: Vec<String> = ["merged", "unmerged", "main", "linked-branch", "missing"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let plans = plan_delete_branches(path, &branches, &DeleteOptions::default(), &[]).unwrap();
+
+        assert_eq!(plans[0].action, DeletionAction::Delete);
+        assert_eq!(plans[0].unreachable_commits, 0);
+        assert_eq!(plans[1].action, DeletionAction::Delete);
+        assert_eq!(plans[1].unreachable_commits, 2);
+        assert_eq!(plans[2].blocked_reason, Some(BlockedReason::Current));
+        assert_eq!(plans[3].blocked_reason, Some(BlockedReason::Worktree));
+        assert!(plans[3].worktree_path.is_some());
+        assert_eq!(plans[4].action, DeletionAction::Blocked);
+        assert_eq!(plans[4].blocked_reason, Some(BlockedReason::NotFound));
+
+        let mut safe = DeleteOptions {
+            mode: DeleteMode::Safe,
+            ..Default::default()
+        };
+        let plans = plan_delete_branches(path, &branches[..2], &safe, &[]).unwrap();
+        assert_eq!(plans[0].action, DeletionAction::Delete);
+        assert_eq!(plans[1].blocked_reason, Some(BlockedReason::NotMerged));
+        assert_eq!(plans[1].unreachable_commits, 0);
+
+        // Merged status follows the base branch the listing used
+        safe.branch_list_options.base_branch = Some("unmerged".to_string());
+        let plans = plan_delete_branches(path, &branches[..2], &safe, &[]).unwrap();
+        assert_eq!(plans[1].action, DeletionAction::Delete);
+
+        let protected = vec!["merged".to_string()];
+        let plans =
+            plan_delete_branches(path, &branches[..2], &DeleteOptions::default(), &protected)
+                .unwrap();
+        assert_eq!(plans[0].blocked_reason, Some(BlockedReason::Protected));
+        assert_eq!(plans[1].action, DeletionAction::Delete);
+
+        // Planning leaves the references alone
+        assert_eq!(run_git(path, &["for-each-ref"]), before);
+    }
+}
@@ -0,0 +1,106 @@
+use git2::{Oid, Repository};
+use std::collections::HashSet;
+
+use crate::shared::error::AppError;
+
+/// Counts the commits that would no longer be reachable from any reference
+/// once some references are deleted, ignoring reflogs.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+/// * `tip` - Commit a deleted reference points to
+/// * `deleted_refs` - Full names of every reference deleted together, e.g. `refs/heads/feature`
+///
+/// # Returns
+///
+/// * `Result<u32, AppError>` - The number of commits only `tip` keeps alive, or an error
+pub fn count_unreachable_commits(
+    repo: &Repository,
+    tip: Oid,
+    deleted_refs: &HashSet<String>,
+) -> Result<u32, AppError> {
+    let mut revwalk = repo.revwalk().map_err(map_reachability_error)?;
+    revwalk.push(tip).map_err(map_reachability_error)?;
+
+    let references = repo.references().map_err(map_reachability_error)?;
+    for reference in references.flatten() {
+        if reference
+            .name()
+            .map(|name| deleted_refs.contains(name))
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        // Symbolic refs resolve to a branch that is hidden on its own, and
+        // refs to non-commit objects cannot keep commits alive
+        if let Ok(commit) = reference.peel_to_commit() {
+            revwalk.hide(commit.id()).map_err(map_reachability_error)?;
+        }
+    }
+
+    // A detached HEAD keeps its commits alive too
+    if let Ok(true) = repo.head_detached() {
+        if let Some(oid) = repo.head().ok().and_then(|head| head.target()) {
+            revwalk.hide(oid).map_err(map_reachability_error)?;
+        }
+    }
+
+    let mut count = 0;
+    for oid in revwalk {
+        oid.map_err(map_reachability_error)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+fn map_reachability_error(e: git2::Error) -> AppError {
+    AppError::new(
+        format!("Failed to count unreachable commits: {}", e),
+        "reachability_failed",
+        Some(e.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_count_unreachable_commits() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+
+        run_git(path, &["checkout", "-b", "feature"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Feature 1"]);
+        run_git(path, &["checkout", "-b", "stacked"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Stacked 1"]);
+        run_git(path, &["checkout", "main"]);
+
+        let repo = Repository::open(path).unwrap();
+        let oid = |rev: &str| Oid::from_str(&run_git(path, &["rev-parse", rev])).unwrap();
+        let deleting = |names: &[&str]| -> HashSet<String> {
+            names.iter().map(|n| format!("refs/heads/{}", n)).collect()
+        };
+
+        // `stacked` still holds the commit of `feature`
+        let count =
+            count_unreachable_commits(&repo, oid("feature"), &deleting(&["feature"])).unwrap();
+        assert_eq!(count, 0);
+
+        let count =
+            count_unreachable_commits(&repo, oid("stacked"), &deleting(&["feature", "stacked"]))
+                .unwrap();
+        assert_eq!(count, 2);
+
+        // A tag keeps commits reachable
+        run_git(path, &["tag", "v1", "stacked"]);
+        let count =
+            count_unreachable_commits(&repo, oid("stacked"), &deleting(&["stacked"])).unwrap();
+        assert_eq!(count, 0);
+    }
+}
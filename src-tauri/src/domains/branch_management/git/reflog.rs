@@ -0,0 +1,122 @@
+use git2::Repository;
+use std::collections::HashMap;
+
+use super::commit::CommitTime;
+
+/// When each branch was last checked out, read once from the HEAD reflog.
+///
+/// Reflogs are local and expire, so a branch missing from the history only
+/// means no checkout was recorded, not that it never happened.
+#[derive(Debug, Default)]
+pub struct CheckoutHistory {
+    last_checkouts: HashMap<String, CommitTime>,
+}
+
+impl CheckoutHistory {
+    /// Reads the HEAD reflog of a repository, yielding an empty history when
+    /// it is missing or unreadable.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The git repository
+    ///
+    /// # Returns
+    ///
+    /// * `CheckoutHistory` - The last checkout time of each branch
+    pub fn load(repo: &Repository) -> Self {
+        let mut last_checkouts = HashMap::new();
+
+        let reflog = match repo.reflog("HEAD") {
+            Ok(reflog) => reflog,
+            Err(_) => return Self::default(),
+        };
+
+        // Entries are ordered newest first, so the first hit is the latest
+        for entry in reflog.iter() {
+            if let Some(name) = entry.message().and_then(parse_checkout_target) {
+                last_checkouts
+                    .entry(name.to_string())
+                    .or_insert_with(|| CommitTime::from(entry.committer().when()));
+            }
+        }
+
+        Self { last_checkouts }
+    }
+
+    pub fn last_checked_out_at(&self, branch_name: &str) -> Option<CommitTime> {
+        self.last_checkouts.get(branch_name).copied()
+    }
+}
+
+/// Reads when a reference was created from its own reflog.
+///
+/// Returns `None` when the reflog is missing or the creation entry has
+/// expired, rather than guessing from the oldest remaining entry.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+/// * `refname` - Full name of the reference, e.g. `refs/heads/main`
+///
+/// # Returns
+///
+/// * `Option<CommitTime>` - When the reference was created, if known
+pub fn get_created_at(repo: &Repository, refname: &str) -> Option<CommitTime> {
+    let reflog = repo.reflog(refname).ok()?;
+    let oldest = reflog.get(reflog.len().checked_sub(1)?)?;
+
+    // Only an entry moving the ref from nothing records its creation
+    if oldest.id_old().is_zero() {
+        Some(CommitTime::from(oldest.committer().when()))
+    } else {
+        None
+    }
+}
+
+/// Extracts the target branch of a `checkout: moving from <old> to <new>`
+/// reflog message, written by both `git checkout` and `git switch`.
+fn parse_checkout_target(message: &str) -> Option<&str> {
+    let moves = message.strip_prefix("checkout: moving from ")?;
+    moves.rsplit_once(" to ").map(|(_, target)| target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_parse_checkout_target() {
+        assert_eq!(
+            parse_checkout_target("checkout: moving from main to feature/a"),
+            Some("feature/a")
+        );
+        assert_eq!(parse_checkout_target("commit: Add feature"), None);
+    }
+
+    #[test]
+    fn test_checkout_history_and_created_at() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+
+        run_git(path, &["branch", "never-checked-out"]);
+        run_git(path, &["checkout", "-b", "feature"]);
+        run_git(path, &["checkout", "main"]);
+        run_git(path, &["switch", "feature"]);
+
+        let repo = Repository::open(path).unwrap();
+        let history = CheckoutHistory::load(&repo);
+        assert!(history.last_checked_out_at("feature").is_some());
+        assert!(history.last_checked_out_at("main").is_some());
+        assert!(history.last_checked_out_at("never-checked-out").is_none());
+
+        assert!(get_created_at(&repo, "refs/heads/feature").is_some());
+
+        // Expired reflogs degrade to unknown instead of failing
+        run_git(path, &["reflog", "expire", "--expire=all", "--all"]);
+        let history = CheckoutHistory::load(&repo);
+        assert!(history.last_checked_out_at("feature").is_none());
+        assert!(get_created_at(&repo, "refs/heads/feature").is_none());
+    }
+}
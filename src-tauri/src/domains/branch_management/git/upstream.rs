@@ -0,0 +1,172 @@
+use git2::{Oid, Repository};
+
+use crate::shared::error::AppError;
+
+/// Tracking information of a local branch, resolved from its
+/// `branch.<name>.remote` and `branch.<name>.merge` configuration.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UpstreamStatus {
+    /// Short name of the upstream, e.g. `origin/main`
+    pub name: Option<String>,
+    /// Commits on the local branch that are not on the upstream
+    pub ahead: Option<u32>,
+    /// Commits on the upstream that are not on the local branch
+    pub behind: Option<u32>,
+    /// Whether the upstream is configured but its ref no longer exists,
+    /// the `[gone]` marker of `git branch -vv`
+    pub gone: bool,
+}
+
+/// Resolves the upstream of a local branch and counts how far apart they are.
+///
+/// Branches without tracking configuration get an empty status instead of an
+/// error, so a single misconfigured branch never breaks the branch listing.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+/// * `branch_name` - Short name of the local branch
+/// * `local_oid` - Commit the local branch points to
+///
+/// # Returns
+///
+/// * `Result<UpstreamStatus, AppError>` - The upstream status or an error
+pub fn get_upstream_status(
+    repo: &Repository,
+    branch_name: &str,
+    local_oid: Oid,
+) -> Result<UpstreamStatus, AppError> {
+    let refname = format!("refs/heads/{}", branch_name);
+
+    let upstream_refname = match repo.branch_upstream_name(&refname) {
+        Ok(buf) => match buf.as_str() {
+            Some(name) => name.to_string(),
+            None => return Ok(UpstreamStatus::default()),
+        },
+        Err(_) => return Ok(UpstreamStatus::default()),
+    };
+
+    let name = Some(shorten_refname(&upstream_refname));
+
+    let upstream_oid = match repo
+        .find_reference(&upstream_refname)
+        .and_then(|reference| reference.peel_to_commit())
+    {
+        Ok(commit) => commit.id(),
+        // The upstream is configured but its ref does not exist locally,
+        // usually because the remote branch was deleted and pruned
+        Err(_) => {
+            return Ok(UpstreamStatus {
+                name,
+                ahead: None,
+                behind: None,
+                gone: true,
+            })
+        }
+    };
+
+    let (ahead, behind) = repo
+        .graph_ahead_behind(local_oid, upstream_oid)
+        .map_err(|e| {
+            AppError::new(
+                format!(
+                    "Failed to compare branch '{}' with its upstream: {}",
+                    branch_name, e
+                ),
+                "ahead_behind_failed",
+                Some(e.to_string()),
+            )
+        })?;
+
+    Ok(UpstreamStatus {
+        name,
+        ahead: Some(ahead as u32),
+        behind: Some(behind as u32),
+        gone: false,
+    })
+}
+
+fn shorten_refname(refname: &str) -> String {
+    refname
+        .strip_prefix("refs/remotes/")
+        .or_else(|| refname.strip_prefix("refs/heads/"))
+        .unwrap_or(refname)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    fn branch_oid(repo: &Repository, name: &str) -> Oid {
+        repo.find_branch(name, git2::BranchType::Local)
+            .unwrap()
+            .get()
+            .peel_to_commit()
+            .unwrap()
+            .id()
+    }
+
+    #[test]
+    fn test_get_upstream_status() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+
+        run_git(
+            path,
+            &["remote", "add", "origin", "https://example.com/repo.git"],
+        );
+        run_git(path, &["update-ref", "refs/remotes/origin/main", "HEAD"]);
+        run_git(path, &["branch", "--set-upstream-to=origin/main", "main"]);
+        run_git(path, &["branch", "stale"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Local work"]);
+
+        // The remote `stale` branch moved on while the local one did not
+        run_git(path, &["update-ref", "refs/remotes/origin/stale", "main"]);
+        run_git(path, &["branch", "--set-upstream-to=origin/stale", "stale"]);
+        run_git(path, &["branch", "untracked"]);
+
+        let repo = Repository::open(path).unwrap();
+
+        let status = get_upstream_status(&repo, "main", branch_oid(&repo, "main")).unwrap();
+        assert_eq!(status.name.as_deref(), Some("origin/main"));
+        assert_eq!(status.ahead, Some(1));
+        assert_eq!(status.behind, Some(0));
+        assert!(!status.gone);
+
+        let status = get_upstream_status(&repo, "stale", branch_oid(&repo, "stale")).unwrap();
+        assert_eq!(status.name.as_deref(), Some("origin/stale"));
+        assert_eq!(status.ahead, Some(0));
+        assert_eq!(status.behind, Some(1));
+
+        let status =
+            get_upstream_status(&repo, "untracked", branch_oid(&repo, "untracked")).unwrap();
+        assert_eq!(status, UpstreamStatus::default());
+    }
+
+    #[test]
+    fn test_get_upstream_status_gone() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+
+        run_git(
+            path,
+            &["remote", "add", "origin", "https://example.com/repo.git"],
+        );
+        run_git(path, &["update-ref", "refs/remotes/origin/feature", "HEAD"]);
+        run_git(path, &["branch", "--track", "feature", "origin/feature"]);
+
+        // Simulate `git fetch --prune` after the remote branch was deleted
+        run_git(path, &["update-ref", "-d", "refs/remotes/origin/feature"]);
+
+        let repo = Repository::open(path).unwrap();
+        let status = get_upstream_status(&repo, "feature", branch_oid(&repo, "feature")).unwrap();
+        assert_eq!(status.name.as_deref(), Some("origin/feature"));
+        assert!(status.gone);
+        assert_eq!(status.ahead, None);
+        assert_eq!(status.behind, None);
+    }
+}
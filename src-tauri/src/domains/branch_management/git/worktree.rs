@@ -0,0 +1,460 @@
+use git2::{Repository, StatusOptions, Worktree, WorktreeLockStatus, WorktreePruneOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::branch::map_repository_open_error;
+use crate::shared::error::AppError;
+
+/// A linked worktree, as listed by `git worktree list`.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorktreeInfo {
+    /// Name of the worktree metadata in `.git/worktrees`
+    pub name: String,
+    pub path: String,
+    /// Branch checked out in the worktree, `None` when detached or unreadable
+    pub branch: Option<String>,
+    pub locked: bool,
+    pub lock_reason: Option<String>,
+    /// Whether the working directory is gone and only stale metadata remains
+    pub prunable: bool,
+}
+
+/// Outcome of pruning one worktree.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WorktreePruneResult {
+    pub name: String,
+    /// The worktree as it was before pruning, `None` when it was not pruned
+    pub pruned: Option<WorktreeInfo>,
+    /// Why the worktree was not pruned
+    pub error: Option<AppError>,
+}
+
+/// Outcome of pruning the stale worktrees of a repository.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneReport {
+    pub pruned_worktrees: Vec<WorktreeInfo>,
+    /// One entry per prunable worktree, in name order
+    pub results: Vec<WorktreePruneResult>,
+}
+
+impl PruneReport {
+    fn push_pruned(&mut self, info: WorktreeInfo) {
+        self.results.push(WorktreePruneResult {
+            name: info.name.clone(),
+            pruned: Some(info.clone()),
+            error: None,
+        });
+        self.pruned_worktrees.push(info);
+    }
+
+    fn push_failed(&mut self, name: &str, error: AppError) {
+        self.results.push(WorktreePruneResult {
+            name: name.to_string(),
+            pruned: None,
+            error: Some(error),
+        });
+    }
+}
+
+/// Maps the branches checked out in other worktrees of the repository to the
+/// path of that worktree.
+///
+/// The worktree `repo` was opened from is left out, its branch is the current
+/// one. Worktrees that cannot be opened, e.g. because their directory was
+/// deleted, are skipped.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+///
+/// # Returns
+///
+/// * `HashMap<String, PathBuf>` - Branch names and the worktree they are checked out in
+pub fn get_worktree_branches(repo: &Repository) -> HashMap<String, PathBuf> {
+    let own_workdir = repo.workdir().and_then(|dir| dir.canonicalize().ok());
+    let mut worktree_branches = HashMap::new();
+
+    let mut checkouts: Vec<Repository> = Vec::new();
+
+    // The main worktree owns the common dir, linked worktrees are registered in it
+    if let Ok(main_repo) = Repository::open(repo.commondir()) {
+        if !main_repo.is_bare() {
+            checkouts.push(main_repo);
+        }
+    }
+
+    if let Ok(names) = repo.worktrees() {
+        for name in names.iter().flatten() {
+            if let Ok(linked_repo) = repo
+                .find_worktree(name)
+                .and_then(|worktree| Repository::open_from_worktree(&worktree))
+            {
+                checkouts.push(linked_repo);
+            }
+        }
+    }
+
+    for checkout in checkouts {
+        let workdir = match checkout.workdir() {
+            Some(dir) => dir.to_path_buf(),
+            None => continue,
+        };
+        if own_workdir.is_some() && workdir.canonicalize().ok() == own_workdir {
+            continue;
+        }
+
+        if let Some(branch_name) = checked_out_branch(&checkout) {
+            worktree_branches.insert(branch_name, normalize_workdir(&workdir));
+        }
+    }
+
+    worktree_branches
+}
+
+/// Lists the linked worktrees of a repository.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+///
+/// # Returns
+///
+/// * `Result<Vec<WorktreeInfo>, AppError>` - The linked worktrees or an error
+pub fn list_worktrees(path: &Path) -> Result<Vec<WorktreeInfo>, AppError> {
+    let repo = Repository::open(path).map_err(|e| map_repository_open_error(path, e))?;
+
+    let names = worktree_names(&repo)?;
+
+    let mut worktrees = Vec::new();
+    for name in names {
+        let worktree = find_worktree(&repo, &name)?;
+        worktrees.push(to_worktree_info(&name, &worktree));
+    }
+
+    Ok(worktrees)
+}
+
+/// Removes a linked worktree: its working directory and its metadata.
+///
+/// Locked worktrees and worktrees with uncommitted changes are refused
+/// unless `force` is set, like `git worktree remove`.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+/// * `name` - Name of the worktree
+/// * `force` - Whether to remove locked or dirty worktrees
+///
+/// # Returns
+///
+/// * `Result<WorktreeInfo, AppError>` - The removed worktree or an error
+pub fn remove_worktree(path: &Path, name: &str, force: bool) -> Result<WorktreeInfo, AppError> {
+    let repo = Repository::open(path).map_err(|e| map_repository_open_error(path, e))?;
+    let worktree = find_worktree(&repo, name)?;
+    let info = to_worktree_info(name, &worktree);
+
+    if !force {
+        if info.locked {
+            return Err(AppError::new(
+                format!("Worktree **{}** is locked", name),
+                "worktree_locked",
+                info.lock_reason.clone(),
+            ));
+        }
+
+        if has_local_changes(&worktree) {
+            return Err(AppError::new(
+                format!("Worktree **{}** has uncommitted changes", name),
+                "worktree_dirty",
+                Some(format!(
+                    "Commit or discard the changes in {} first",
+                    info.path
+                )),
+            ));
+        }
+    }
+
+    worktree
+        .prune(Some(
+            WorktreePruneOptions::new()
+                .valid(true)
+                .locked(force)
+                .working_tree(true),
+        ))
+        .map_err(|e| {
+            AppError::new(
+                format!("Failed to remove worktree '{}': {}", name, e),
+                "worktree_remove_failed",
+                Some(e.to_string()),
+            )
+        })?;
+
+    Ok(info)
+}
+
+/// Deletes the metadata of worktrees whose directory no longer exists, like
+/// `git worktree prune`. Locked worktrees are kept.
+///
+/// A worktree that cannot be pruned is reported and the others are still
+/// pruned.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+///
+/// # Returns
+///
+/// * `Result<PruneReport, AppError>` - The outcome for each prunable worktree or an error
+pub fn prune_worktrees(path: &Path) -> Result<PruneReport, AppError> {
+    let repo = Repository::open(path).map_err(|e| map_repository_open_error(path, e))?;
+    let mut report = PruneReport::default();
+
+    for name in worktree_names(&repo)? {
+        let worktree = match find_worktree(&repo, &name) {
+            Ok(worktree) => worktree,
+            Err(e) => {
+                report.push_failed(&name, e);
+                continue;
+            }
+        };
+
+        let info = to_worktree_info(&name, &worktree);
+        if !info.prunable {
+            continue;
+        }
+
+        match worktree.prune(None) {
+            Ok(()) => report.push_pruned(info),
+            Err(e) => report.push_failed(
+                &name,
+                AppError::new(
+                    format!("Failed to prune worktree '{}': {}", name, e),
+                    "worktree_prune_failed",
+                    Some(e.to_string()),
+                ),
+            ),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Names of the linked worktrees, sorted, skipping names that are not UTF-8.
+fn worktree_names(repo: &Repository) -> Result<Vec<String>, AppError> {
+    let names = repo.worktrees().map_err(|e| {
+        AppError::new(
+            format!("Failed to list worktrees: {}", e),
+            "worktree_list_failed",
+            Some(e.to_string()),
+        )
+    })?;
+
+    let mut names: Vec<String> = names
+        .iter()
+        .flatten()
+        .map(|name| name.to_string())
+        .collect();
+    names.sort();
+
+    Ok(names)
+}
+
+fn find_worktree(repo: &Repository, name: &str) -> Result<Worktree, AppError> {
+    repo.find_worktree(name).map_err(|e| {
+        AppError::new(
+            format!("Worktree **{}** not found", name),
+            "worktree_not_found",
+            Some(e.to_string()),
+        )
+    })
+}
+
+fn to_worktree_info(name: &str, worktree: &Worktree) -> WorktreeInfo {
+    let lock_reason = match worktree.is_locked() {
+        // The lock file keeps the newline `git worktree lock` writes
+        Ok(WorktreeLockStatus::Locked(reason)) => {
+            Some(reason.map(|reason| reason.trim_end().to_string()))
+        }
+        _ => None,
+    };
+
+    WorktreeInfo {
+        name: name.to_string(),
+        path: normalize_workdir(worktree.path()).display().to_string(),
+        branch: Repository::open_from_worktree(worktree)
+            .ok()
+            .and_then(|repo| checked_out_branch(&repo)),
+        locked: lock_reason.is_some(),
+        lock_reason: lock_reason.flatten(),
+        prunable: worktree.is_prunable(None).unwrap_or(false),
+    }
+}
+
+/// Checks for modified, staged or untracked files, treating an unreadable
+/// worktree as clean since there is nothing left to lose.
+fn has_local_changes(worktree: &Worktree) -> bool {
+    let repo = match Repository::open_from_worktree(worktree) {
+        Ok(repo) => repo,
+        Err(_) => return false,
+    };
+
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).include_ignored(false);
+
+    repo.statuses(Some(&mut options))
+        .map(|statuses| !statuses.is_empty())
+        .unwrap_or(false)
+}
+
+/// Reads the branch HEAD points to, even when that branch has no commit yet.
+fn checked_out_branch(repo: &Repository) -> Option<String> {
+    let head = repo.find_reference("HEAD").ok()?;
+    head.symbolic_target()
+        .and_then(|target| target.strip_prefix("refs/heads/"))
+        .map(|name| name.to_string())
+}
+
+/// Drops the trailing separator libgit2 keeps on working directories.
+fn normalize_workdir(workdir: &Path) -> PathBuf {
+    workdir.components().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_get_worktree_branches() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        let worktrees_dir = tempfile::tempdir().unwrap();
+        let linked_path = worktrees_dir.path().join("linked");
+
+        run_git(
+            path,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                "linked-branch",
+                linked_path.to_str().unwrap(),
+            ],
+        );
+
+        let repo = Repository::open(path).unwrap();
+        let branches = get_worktree_branches(&repo);
+        assert_eq!(branches.len(), 1);
+        assert_eq!(
+            branches["linked-branch"].canonicalize().unwrap(),
+            linked_path.canonicalize().unwrap()
+        );
+
+        // From the linked worktree, the main worktree is the other one
+        let linked_repo = Repository::open(&linked_path).unwrap();
+        let branches = get_worktree_branches(&linked_repo);
+        assert_eq!(branches.len(), 1);
+        assert_eq!(
+            branches["main"].canonicalize().unwrap(),
+            path.canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_and_remove_worktrees() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        let worktrees_dir = tempfile::tempdir().unwrap();
+        let linked_path = worktrees_dir.path().join("linked");
+        let locked_path = worktrees_dir.path().join("locked");
+
+        for (branch, worktree_path) in [
+            ("linked-branch", &linked_path),
+            ("locked-branch", &locked_path),
+        ] {
+            run_git(
+                path,
+                &[
+                    "worktree",
+                    "add",
+                    "-b",
+                    branch,
+                    worktree_path.to_str().unwrap(),
+                ],
+            );
+        }
+        run_git(
+            path,
+            &[
+                "worktree",
+                "lock",
+                "--reason",
+                "On a USB drive",
+                locked_path.to_str().unwrap(),
+            ],
+        );
+
+        let worktrees = list_worktrees(path).unwrap();
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[0].name, "linked");
+        assert_eq!(worktrees[0].branch.as_deref(), Some("linked-branch"));
+        assert!(!worktrees[0].locked);
+        assert!(!worktrees[0].prunable);
+        assert_eq!(worktrees[1].lock_reason.as_deref(), Some("On a USB drive"));
+
+        let result = remove_worktree(path, "locked", false);
+        assert_eq!(result.unwrap_err().kind, "worktree_locked");
+
+        std::fs::write(linked_path.join("wip.txt"), "wip").unwrap();
+        let result = remove_worktree(path, "linked", false);
+        assert_eq!(result.unwrap_err().kind, "worktree_dirty");
+
+        let removed = remove_worktree(path, "linked", true).unwrap();
+        assert_eq!(removed.name, "linked");
+        assert!(!linked_path.exists());
+        assert_eq!(list_worktrees(path).unwrap().len(), 1);
+
+        let result = remove_worktree(path, "missing", false);
+        assert_eq!(result.unwrap_err().kind, "worktree_not_found");
+    }
+
+    #[test]
+    fn test_prune_worktrees() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        let worktrees_dir = tempfile::tempdir().unwrap();
+        let stale_path = worktrees_dir.path().join("stale");
+        let kept_path = worktrees_dir.path().join("kept");
+
+        for worktree_path in [&stale_path, &kept_path] {
+            run_git(
+                path,
+                &[
+                    "worktree",
+                    "add",
+                    "--detach",
+                    worktree_path.to_str().unwrap(),
+                ],
+            );
+        }
+        std::fs::remove_dir_all(&stale_path).unwrap();
+
+        let report = prune_worktrees(path).unwrap();
+        assert_eq!(report.pruned_worktrees.len(), 1);
+        assert_eq!(report.pruned_worktrees[0].name, "stale");
+        assert_eq!(report.results.len(), 1);
+        assert!(report.results[0].error.is_none());
+
+        let worktrees = list_worktrees(path).unwrap();
+        assert_eq!(worktrees.len(), 1);
+        assert_eq!(worktrees[0].name, "kept");
+        assert_eq!(worktrees[0].branch, None);
+    }
+}
@@ -0,0 +1,276 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::super::git::base::get_base_branches;
+use super::super::git::branch::{get_all_branches_with_last_commit, Branch, BranchListOptions};
+use super::super::git::merge::MergeDetection;
+use super::deletion::{
+    BranchDeletionResult, DeleteMode, DeleteOptions, DeletedBranchInfo, DeletionReport,
+};
+use super::protection::{BranchProtection, ProtectionRules};
+use crate::shared::error::AppError;
+use crate::shared::utils::glob::glob_match;
+
+/// Which branches a cleanup plan deletes.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum CleanupTarget {
+    /// Branches merged into the base branch
+    Merged,
+    /// Branches whose upstream was deleted
+    Gone,
+    /// Branches that are merged, or whose upstream was deleted
+    MergedOrGone,
+}
+
+/// A cleanup applied the same way to every repository.
+///
+/// Protected branches, the base branch and branches checked out in another
+/// worktree are never deleted. Each repository keeps the branches it could
+/// delete unless the plan is atomic.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupPlan {
+    pub target: CleanupTarget,
+    /// Glob patterns of branches to keep, e.g. `release/*`
+    pub exclude_patterns: Vec<String>,
+    /// Branch to measure merged status against, auto-detected per repository when `None`
+    pub base_branch: Option<String>,
+    /// Merge detection mode, `Ancestry` when `None`
+    pub merge_detection: Option<MergeDetection>,
+    /// Branches to keep, only the current and default branch when `None`
+    pub protection: Option<ProtectionRules>,
+    /// Delete all selected branches of a repository or none of them, `false` when `None`
+    pub atomic: Option<bool>,
+}
+
+/// Outcome of a cleanup in one repository.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryCleanupResult {
+    pub path: String,
+    pub deleted_branches: Vec<DeletedBranchInfo>,
+    /// Outcome of each selected branch, including the ones left in place
+    pub results: Vec<BranchDeletionResult>,
+    /// Why the repository could not be cleaned, nothing was deleted in it then
+    pub error: Option<AppError>,
+}
+
+/// Applies a cleanup plan to several repositories.
+///
+/// Repositories are cleaned one after the other, and a failure in one of them
+/// is reported in its result instead of stopping the batch.
+///
+/// # Arguments
+///
+/// * `paths` - Paths to the git repositories
+/// * `plan` - The cleanup plan to apply
+///
+/// # Returns
+///
+/// * `Vec<RepositoryCleanupResult>` - One result per repository, in the order of `paths`
+pub fn cleanup_repositories(paths: &[String], plan: &CleanupPlan) -> Vec<RepositoryCleanupResult> {
+    paths
+        .iter()
+        .map(|path| match cleanup_repository(Path::new(path), plan) {
+            Ok(report) => RepositoryCleanupResult {
+                path: path.clone(),
+                deleted_branches: report.deleted_branches,
+                results: report.results,
+                error: None,
+            },
+            Err(e) => RepositoryCleanupResult {
+                path: path.clone(),
+                deleted_branches: Vec::new(),
+                results: Vec::new(),
+                error: Some(e),
+            },
+        })
+        .collect()
+}
+
+fn cleanup_repository(path: &Path, plan: &CleanupPlan) -> Result<DeletionReport, AppError> {
+    let branches = select_branches(path, plan)?;
+    // Gone branches are often unmerged, the plan itself is the safeguard
+    let options = DeleteOptions {
+        mode: DeleteMode::Force,
+        atomic: plan.atomic.unwrap_or(false),
+        ..Default::default()
+    };
+    super::deletion::delete_branches(
+        path,
+        &branches,
+        &options,
+        &plan.protection.clone().unwrap_or_default(),
+    )
+}
+
+/// Selects the branches of a repository that a cleanup plan would delete.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+/// * `plan` - The cleanup plan
+///
+/// # Returns
+///
+/// * `Result<Vec<String>, AppError>` - Names of the branches to delete or an error
+pub fn select_branches(path: &Path, plan: &CleanupPlan) -> Result<Vec<String>, AppError> {
+    let base_branches = get_base_branches(path, plan.base_branch.as_deref())?;
+    // The base is often a remote-tracking branch, its local branch must be kept too
+    let local_base_branch = base_branches.selected_local;
+    let options = BranchListOptions {
+        base_branch: base_branches.selected,
+        merge_detection: plan.merge_detection.unwrap_or_default(),
+    };
+
+    let mut branches = get_all_branches_with_last_commit(path, &options)?;
+    BranchProtection::load(path, plan.protection.clone().unwrap_or_default())?.apply(&mut branches);
+
+    Ok(branches
+        .iter()
+        .filter(|branch| is_target(branch, plan.target))
+        .filter(|branch| !branch.protected && branch.worktree_path.is_none())
+        .filter(|branch| local_base_branch.as_deref() != Some(branch.name.as_str()))
+        .filter(|branch| {
+            !plan
+                .exclude_patterns
+                .iter()
+                .any(|pattern| glob_match(pattern, &branch.name))
+        })
+        .map(|branch| branch.name.clone())
+        .collect())
+}
+
+fn is_target(branch: &Branch, target: CleanupTarget) -> bool {
+    match target {
+        CleanupTarget::Merged => branch.fully_merged,
+        CleanupTarget::Gone => branch.upstream_gone,
+        CleanupTarget::MergedOrGone => branch.fully_merged || branch.upstream_gone,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_cleanup_repositories() {
+        let _guard = DirectoryGuard::new();
+        let first = setup_test_repo();
+        let second = setup_test_repo();
+        for dir in [&first, &second] {
+            run_git(dir.path(), &["config", "init.defaultBranch", "main"]);
+        }
+
+        run_git(first.path(), &["branch", "merged"]);
+        run_git(first.path(), &["branch", "release/1.0"]);
+        run_git(first.path(), &["checkout", "-b", "unmerged"]);
+        run_git(first.path(), &["commit", "--allow-empty", "-m", "Work"]);
+        run_git(first.path(), &["checkout", "main"]);
+        run_git(second.path(), &["branch", "also-merged"]);
+
+        let plan = CleanupPlan {
+            target: CleanupTarget::Merged,
+            exclude_patterns: vec!["release/*".to_string()],
+            base_branch: None,
+            merge_detection: None,
+            protection: None,
+            atomic: None,
+        };
+        let missing = first.path().join("missing").display().to_string();
+        let paths = vec![
+            first.path().display().to_string(),
+            missing.clone(),
+            second.path().display().to_string(),
+        ];
+
+        let results = cleanup_repositories(&paths, &plan);
+        assert_eq!(results.len(), 3);
+
+        let deleted: Vec<&str> = results[0]
+            .deleted_branches
+            .iter()
+            .map(|info| info.branch.name.as_str())
+            .collect();
+        assert_eq!(deleted, vec!["merged"]);
+        assert_eq!(results[0].results.len(), 1);
+        assert!(results[0].error.is_none());
+
+        // A failing repository does not stop the batch
+        assert_eq!(results[1].path, missing);
+        assert!(results[1].error.is_some());
+        assert_eq!(results[2].deleted_branches.len(), 1);
+
+        // The plan's protection rules keep matching branches
+        run_git(second.path(), &["branch", "kept"]);
+        run_git(second.path(), &["branch", "dropped"]);
+        let plan = CleanupPlan {
+            protection: Some(ProtectionRules {
+                names: vec!["kept".to_string()],
+                ..Default::default()
+            }),
+            ..plan
+        };
+        let results = cleanup_repositories(&paths[2..], &plan);
+        let deleted: Vec<&str> = results[0]
+            .results
+            .iter()
+            .filter(|result| result.deleted.is_some())
+            .map(|result| result.branch.as_str())
+            .collect();
+        assert_eq!(deleted, vec!["dropped"]);
+        assert!(run_git(second.path(), &["branch", "--list", "kept"]).contains("kept"));
+    }
+
+    #[test]
+    fn test_select_branches_keeps_local_base_of_remote_base() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        let remote_dir = tempfile::tempdir().unwrap();
+        run_git(remote_dir.path(), &["init", "--bare"]);
+        run_git(
+            path,
+            &[
+                "remote",
+                "add",
+                "origin",
+                remote_dir.path().to_str().unwrap(),
+            ],
+        );
+        run_git(path, &["branch", "develop"]);
+        run_git(path, &["branch", "merged"]);
+        run_git(path, &["push", "-u", "origin", "main", "develop"]);
+        run_git(
+            path,
+            &[
+                "symbolic-ref",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/main",
+            ],
+        );
+        run_git(path, &["checkout", "-b", "feature"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Work"]);
+
+        let plan = CleanupPlan {
+            target: CleanupTarget::Merged,
+            exclude_patterns: Vec::new(),
+            base_branch: Some("origin/develop".to_string()),
+            merge_detection: None,
+            protection: None,
+            atomic: None,
+        };
+        assert_eq!(select_branches(path, &plan).unwrap(), vec!["merged"]);
+
+        let plan = CleanupPlan {
+            base_branch: None,
+            ..plan
+        };
+        assert_eq!(
+            select_branches(path, &plan).unwrap(),
+            vec!["develop", "merged"]
+        );
+    }
+}
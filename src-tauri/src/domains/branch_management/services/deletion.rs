@@ -1,4 +1,6 @@
-use super::super::git::branch::Branch;
+use super::super::git::branch::{Branch, BranchListOptions};
+use super::super::git::plan::BranchDeletionPlan;
+use super::protection::{BranchProtection, ProtectionRules};
 use crate::shared::error::AppError;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -10,6 +12,69 @@ pub struct DeletedBranchInfo {
     pub raw_output: String,
 }
 
+/// How `delete_branches` treats branches that are not merged.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum DeleteMode {
+    /// Refuse branches whose tip is in neither the base branch nor their
+    /// upstream, like `git branch -d`. Squashed and rebased branches are
+    /// refused too.
+    Safe,
+    /// Delete branches whatever their merged status, like `git branch -D`
+    #[default]
+    Force,
+}
+
+/// How `delete_branches` deletes a batch of branches.
+#[derive(Clone, Debug, Default)]
+pub struct DeleteOptions {
+    pub mode: DeleteMode,
+    /// Delete all branches or none of them, instead of as many as possible
+    pub atomic: bool,
+    /// Base branch and merge detection `Safe` mode measures merged status
+    /// with, the same the branch listing used
+    pub branch_list_options: BranchListOptions,
+}
+
+/// Outcome of deleting one branch.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchDeletionResult {
+    pub branch: String,
+    /// The branch as it was before its deletion, `None` when it was not deleted
+    pub deleted: Option<DeletedBranchInfo>,
+    /// Why the branch was not deleted
+    pub error: Option<AppError>,
+}
+
+/// Outcome of deleting a batch of branches.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletionReport {
+    pub deleted_branches: Vec<DeletedBranchInfo>,
+    /// One result per branch, in the order they were requested
+    pub results: Vec<BranchDeletionResult>,
+}
+
+impl DeletionReport {
+    pub fn push_deleted(&mut self, info: DeletedBranchInfo) {
+        self.results.push(BranchDeletionResult {
+            branch: info.branch.name.clone(),
+            deleted: Some(info.clone()),
+            error: None,
+        });
+        self.deleted_branches.push(info);
+    }
+
+    pub fn push_failed(&mut self, branch: &str, error: AppError) {
+        self.results.push(BranchDeletionResult {
+            branch: branch.to_string(),
+            deleted: None,
+            error: Some(error),
+        });
+    }
+}
+
 #[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub enum ConflictResolution {
@@ -48,17 +113,136 @@ pub struct ConflictDetails {
 
 /// Delete branches from a git repository.
 ///
+/// Protected branches are reported as failed without reaching git, or fail
+/// the whole batch when it is atomic.
+///
 /// # Arguments
 ///
 /// * `path` - Path to the git repository
 /// * `branches` - List of branch names to delete
+/// * `options` - Whether unmerged branches are refused, and whether the batch is atomic
+/// * `rules` - The branches to protect
 ///
 /// # Returns
 ///
-/// * `Result<Vec<DeletedBranchInfo>, AppError>` - Information about deleted branches or an error
+/// * `Result<DeletionReport, AppError>` - The outcome of each branch or an error
 pub fn delete_branches(
     path: &Path,
     branches: &[String],
-) -> Result<Vec<DeletedBranchInfo>, AppError> {
-    super::super::git::branch::delete_branches(path, branches)
+    options: &DeleteOptions,
+    rules: &ProtectionRules,
+) -> Result<DeletionReport, AppError> {
+    let protection = BranchProtection::load(path, rules.clone())?;
+
+    if options.atomic {
+        for branch in branches {
+            protection.check(branch)?;
+        }
+    }
+
+    let unprotected: Vec<String> = branches
+        .iter()
+        .filter(|branch| !protection.is_protected(branch))
+        .cloned()
+        .collect();
+    let git_report = super::super::git::branch::delete_branches(path, &unprotected, options)?;
+
+    // Put the protected branches back in the requested order
+    let mut git_results = git_report.results.into_iter();
+    let mut report = DeletionReport {
+        deleted_branches: git_report.deleted_branches,
+        results: Vec::new(),
+    };
+    for branch in branches {
+        match protection.check(branch) {
+            Ok(()) => report.results.extend(git_results.next()),
+            Err(e) => report.push_failed(branch, e),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Plan the deletion of branches from a git repository without deleting them.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+/// * `branches` - List of branch names to delete
+/// * `options` - Whether and how unmerged branches are refused, atomicity does not change the plan
+/// * `rules` - The branches to protect
+///
+/// # Returns
+///
+/// * `Result<Vec<BranchDeletionPlan>, AppError>` - What would happen to each branch or an error
+pub fn plan_delete_branches(
+    path: &Path,
+    branches: &[String],
+    options: &DeleteOptions,
+    rules: &ProtectionRules,
+) -> Result<Vec<BranchDeletionPlan>, AppError> {
+    let protection = BranchProtection::load(path, rules.clone())?;
+    let protected_branches: Vec<String> = branches
+        .iter()
+        .filter(|branch| protection.is_protected(branch))
+        .cloned()
+        .collect();
+
+    super::super::git::plan::plan_delete_branches(path, branches, options, &protected_branches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_delete_branches_keeps_protected_branches() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        run_git(path, &["config", "init.defaultBranch", "main"]);
+        run_git(path, &["branch", "feature"]);
+        run_git(path, &["branch", "release/1.0"]);
+        run_git(path, &["checkout", "-b", "develop"]);
+
+        let rules = ProtectionRules {
+            patterns: vec!["release/*".to_string()],
+            ..Default::default()
+        };
+        let branches: Vec<String> = ["main", "release/1.0", "feature", "develop"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        let atomic = DeleteOptions {
+            atomic: true,
+            ..Default::default()
+        };
+        let result = delete_branches(path, &branches, &atomic, &rules);
+        assert_eq!(result.unwrap_err().kind, "protected_branch");
+
+        let report = delete_branches(path, &branches, &DeleteOptions::default(), &rules).unwrap();
+        let outcomes: Vec<(&str, Option<&str>)> = report
+            .results
+            .iter()
+            .map(|result| {
+                (
+                    result.branch.as_str(),
+                    result.error.as_ref().map(|e| e.kind.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("main", Some("protected_branch")),
+                ("release/1.0", Some("protected_branch")),
+                ("feature", None),
+                ("develop", Some("protected_branch")),
+            ]
+        );
+        assert_eq!(report.deleted_branches.len(), 1);
+        assert_eq!(run_git(path, &["branch", "--list", "main"]), "main");
+    }
 }
@@ -0,0 +1,17 @@
+use crate::shared::error::AppError;
+use std::path::Path;
+
+/// Lock or unlock a branch so protection rules keep it from being deleted.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository
+/// * `branch` - The branch name to lock or unlock
+/// * `locked` - Whether to lock or unlock the branch
+///
+/// # Returns
+///
+/// * `Result<(), AppError>` - Nothing, or an error
+pub fn set_branch_locked(path: &Path, branch: &str, locked: bool) -> Result<(), AppError> {
+    super::super::git::lock::set_branch_locked(path, branch, locked)
+}
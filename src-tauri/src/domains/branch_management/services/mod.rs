@@ -1,3 +1,6 @@
+pub mod cleanup;
 pub mod deletion;
+pub mod locking;
+pub mod protection;
 pub mod restoration;
 pub mod switching;
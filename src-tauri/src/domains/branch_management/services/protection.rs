@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::super::git::base::get_base_branches;
+use super::super::git::branch::Branch;
+use super::super::git::head::get_head_state;
+use super::super::git::lock::list_locked_branches;
+use crate::shared::error::AppError;
+use crate::shared::utils::glob::glob_match;
+
+/// Branches the backend refuses to delete, whichever client asks.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtectionRules {
+    /// Protected branch names, e.g. `main`
+    pub names: Vec<String>,
+    /// Glob patterns of protected branches, e.g. `release/*`
+    pub patterns: Vec<String>,
+    /// Whether the checked out branch is protected
+    pub current_branch: bool,
+    /// Whether the default branch of the repository is protected
+    pub default_branch: bool,
+    /// Whether branches locked in the repository's config are protected
+    pub locked_branches: bool,
+}
+
+impl Default for ProtectionRules {
+    fn default() -> Self {
+        Self {
+            names: Vec::new(),
+            patterns: Vec::new(),
+            current_branch: true,
+            default_branch: true,
+            locked_branches: true,
+        }
+    }
+}
+
+/// Protection rules resolved against one repository.
+#[derive(Debug, Clone)]
+pub struct BranchProtection {
+    rules: ProtectionRules,
+    current_branch: Option<String>,
+    default_branch: Option<String>,
+    locked_branches: HashSet<String>,
+}
+
+impl BranchProtection {
+    /// Resolves the current, default and locked branches the rules refer to.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the git repository
+    /// * `rules` - The protection rules
+    ///
+    /// # Returns
+    ///
+    /// * `Result<BranchProtection, AppError>` - The resolved rules or an error
+    pub fn load(path: &Path, rules: ProtectionRules) -> Result<Self, AppError> {
+        let current_branch = if rules.current_branch {
+            get_head_state(path)?
+                .branch_name()
+                .map(|name| name.to_string())
+        } else {
+            None
+        };
+        let default_branch = if rules.default_branch {
+            // The detected base is often `origin/main`, the local `main` is the one at risk
+            get_base_branches(path, None)?.selected_local
+        } else {
+            None
+        };
+        let locked_branches = if rules.locked_branches {
+            list_locked_branches(path)?
+        } else {
+            HashSet::new()
+        };
+
+        Ok(Self {
+            rules,
+            current_branch,
+            default_branch,
+            locked_branches,
+        })
+    }
+
+    /// Why a branch is protected, `None` when it may be deleted.
+    pub fn reason(&self, branch_name: &str) -> Option<String> {
+        if self.current_branch.as_deref() == Some(branch_name) {
+            return Some("It is the current branch".to_string());
+        }
+        if self.default_branch.as_deref() == Some(branch_name) {
+            return Some("It is the default branch of the repository".to_string());
+        }
+        if self.locked_branches.contains(branch_name) {
+            return Some("It is locked".to_string());
+        }
+        if self.rules.names.iter().any(|name| name == branch_name) {
+            return Some("It is in the protected branches".to_string());
+        }
+        self.rules
+            .patterns
+            .iter()
+            .find(|pattern| glob_match(pattern, branch_name))
+            .map(|pattern| format!("It matches the protected pattern {}", pattern))
+    }
+
+    pub fn is_protected(&self, branch_name: &str) -> bool {
+        self.reason(branch_name).is_some()
+    }
+
+    /// Fails with a `protected_branch` error when a branch is protected.
+    pub fn check(&self, branch_name: &str) -> Result<(), AppError> {
+        match self.reason(branch_name) {
+            Some(reason) => Err(AppError::new(
+                format!("Branch **{}** is protected", branch_name),
+                "protected_branch",
+                Some(reason),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets the `protected` flag of listed local branches.
+    pub fn apply(&self, branches: &mut [Branch]) {
+        for branch in branches.iter_mut().filter(|branch| branch.remote.is_none()) {
+            branch.protected = self.is_protected(&branch.name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_branch_protection() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        run_git(path, &["config", "init.defaultBranch", "main"]);
+        run_git(path, &["checkout", "-b", "feature"]);
+
+        let rules = ProtectionRules {
+            names: vec!["develop".to_string()],
+            patterns: vec!["release/*".to_string()],
+            ..Default::default()
+        };
+        let protection = BranchProtection::load(path, rules).unwrap();
+
+        assert!(protection.is_protected("feature"));
+        assert!(protection.is_protected("main"));
+        assert!(protection.is_protected("develop"));
+        assert!(protection.is_protected("release/1.0"));
+        assert!(!protection.is_protected("release-notes"));
+        assert_eq!(
+            protection.check("main").unwrap_err().kind,
+            "protected_branch"
+        );
+        assert!(protection.check("other").is_ok());
+
+        run_git(path, &["branch", "locked"]);
+        run_git(path, &["config", "branch.locked.cutBranchesLocked", "true"]);
+        let protection = BranchProtection::load(path, ProtectionRules::default()).unwrap();
+        assert!(protection.is_protected("locked"));
+
+        let rules = ProtectionRules {
+            current_branch: false,
+            default_branch: false,
+            locked_branches: false,
+            ..Default::default()
+        };
+        let protection = BranchProtection::load(path, rules).unwrap();
+        assert!(!protection.is_protected("feature"));
+        assert!(!protection.is_protected("main"));
+        assert!(!protection.is_protected("locked"));
+    }
+
+    #[test]
+    fn test_default_branch_protection_with_remote() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        let remote_dir = tempfile::tempdir().unwrap();
+        run_git(remote_dir.path(), &["init", "--bare"]);
+        run_git(
+            path,
+            &[
+                "remote",
+                "add",
+                "origin",
+                remote_dir.path().to_str().unwrap(),
+            ],
+        );
+        run_git(path, &["push", "-u", "origin", "main"]);
+        run_git(
+            path,
+            &[
+                "symbolic-ref",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/main",
+            ],
+        );
+        run_git(path, &["checkout", "-b", "feature"]);
+
+        let protection = BranchProtection::load(path, ProtectionRules::default()).unwrap();
+        assert_eq!(
+            protection.reason("main").as_deref(),
+            Some("It is the default branch of the repository")
+        );
+    }
+}
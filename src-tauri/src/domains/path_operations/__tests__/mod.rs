@@ -1 +1,127 @@
-// Tests for path operations domain
\ No newline at end of file
+// Tests for path operations domain
+use std::path::Path;
+
+use super::service::{get_root_path, RootPathResponse};
+use crate::shared::git::open_repository_with_ceilings;
+use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+fn canonical_root(response: &RootPathResponse) -> std::path::PathBuf {
+    Path::new(&response.root_path).canonicalize().unwrap()
+}
+
+#[tokio::test]
+async fn test_get_root_path_from_subdirectory() {
+    let _guard = DirectoryGuard::new();
+    let dir = setup_test_repo();
+    let nested = dir.path().join("src").join("nested");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let response = get_root_path(nested.display().to_string()).await.unwrap();
+    assert_eq!(
+        canonical_root(&response),
+        dir.path().canonicalize().unwrap()
+    );
+
+    // Picking the git dir itself resolves to the working directory
+    let response = get_root_path(dir.path().join(".git").display().to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        canonical_root(&response),
+        dir.path().canonicalize().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_get_root_path_follows_gitfiles() {
+    let _guard = DirectoryGuard::new();
+    let dir = setup_test_repo();
+    let other_dir = tempfile::tempdir().unwrap();
+
+    // Linked worktree: `.git` is a file pointing into the main git dir
+    let worktree_path = other_dir.path().join("worktree");
+    run_git(
+        dir.path(),
+        &[
+            "worktree",
+            "add",
+            "--detach",
+            worktree_path.to_str().unwrap(),
+        ],
+    );
+    std::fs::create_dir(worktree_path.join("sub")).unwrap();
+
+    let response = get_root_path(worktree_path.join("sub").display().to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        canonical_root(&response),
+        worktree_path.canonicalize().unwrap()
+    );
+
+    // Separate git dir, as set up by `git init --separate-git-dir`
+    let work_path = other_dir.path().join("work");
+    let git_dir_path = other_dir.path().join("repo.git");
+    std::fs::create_dir(&work_path).unwrap();
+    run_git(
+        &work_path,
+        &["init", "--separate-git-dir", git_dir_path.to_str().unwrap()],
+    );
+
+    let response = get_root_path(work_path.display().to_string())
+        .await
+        .unwrap();
+    assert!(!response.is_bare);
+    assert_eq!(canonical_root(&response), work_path.canonicalize().unwrap());
+}
+
+#[test]
+fn test_open_repository_stops_at_ceilings() {
+    let _guard = DirectoryGuard::new();
+    let dir = setup_test_repo();
+    let nested = dir.path().join("nested");
+    std::fs::create_dir(&nested).unwrap();
+
+    assert!(open_repository_with_ceilings(&nested, &[]).is_ok());
+
+    let result = open_repository_with_ceilings(&nested, &[dir.path().to_path_buf()]);
+    assert_eq!(result.err().unwrap().code(), git2::ErrorCode::NotFound);
+
+    // A ceiling never hides the repository at the path itself
+    assert!(open_repository_with_ceilings(dir.path(), &[dir.path().to_path_buf()]).is_ok());
+}
+
+#[tokio::test]
+async fn test_get_root_path_outside_repository() {
+    let _guard = DirectoryGuard::new();
+    let dir = tempfile::tempdir().unwrap();
+
+    let result = get_root_path(dir.path().display().to_string()).await;
+    assert_eq!(result.unwrap_err().kind, "is_not_git_repository");
+}
+
+#[tokio::test]
+async fn test_get_root_path_of_bare_repository() {
+    let _guard = DirectoryGuard::new();
+    let dir = setup_test_repo();
+    let bare_dir = tempfile::tempdir().unwrap();
+    let bare_path = bare_dir.path().join("mirror.git");
+    run_git(
+        dir.path(),
+        &["clone", "--bare", ".", bare_path.to_str().unwrap()],
+    );
+
+    let response = get_root_path(bare_path.display().to_string())
+        .await
+        .unwrap();
+    assert!(response.is_bare);
+    assert_eq!(
+        Path::new(&response.root_path).canonicalize().unwrap(),
+        bare_path.canonicalize().unwrap()
+    );
+
+    let response = get_root_path(dir.path().display().to_string())
+        .await
+        .unwrap();
+    assert!(!response.is_bare);
+}
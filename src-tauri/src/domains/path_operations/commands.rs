@@ -13,7 +13,10 @@ pub struct GetRepositoryRootInput {
 #[serde(rename_all = "camelCase")]
 pub struct GetRepositoryRootOutput {
     pub root_path: String,
+    /// Stable id derived from the canonical root path, shared with `get_repository`
     pub id: Option<String>,
+    /// First commit of the history, to recognise the repository after a move
+    pub root_commit: Option<String>,
 }
 
 /// Gets the root path of a git repository.
@@ -36,5 +39,6 @@ pub async fn get_repository_root(
     Ok(GetRepositoryRootOutput {
         root_path: response.root_path,
         id: response.id,
+        root_commit: response.root_commit,
     })
 }
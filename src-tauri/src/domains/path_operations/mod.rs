@@ -2,4 +2,7 @@ pub mod commands;
 pub mod service;
 pub mod types;
 
+#[cfg(test)]
+mod __tests__;
+
 pub use commands::*;
@@ -1,5 +1,3 @@
-use git2::Repository;
-use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::path::Path;
 
@@ -9,7 +7,12 @@ use crate::shared::error::AppError;
 #[serde(rename_all = "camelCase")]
 pub struct RootPathResponse {
     pub root_path: String,
+    /// Stable id derived from the canonical root path
     pub id: Option<String>,
+    /// First commit of the history, to recognise the repository after a move
+    pub root_commit: Option<String>,
+    /// Bare repositories have no working directory, their root is the git dir
+    pub is_bare: bool,
 }
 
 impl PartialEq for RootPathResponse {
@@ -26,23 +29,8 @@ impl Hash for RootPathResponse {
     }
 }
 
-/// Calculates a hash for a hashable type.
-///
-/// # Arguments
-///
-/// * `t` - The value to hash
-///
-/// # Returns
-///
-/// * `u64` - The calculated hash
-fn calculate_hash<T: Hash + ?Sized>(t: &T) -> u64 {
-    let mut s = DefaultHasher::new();
-    t.hash(&mut s);
-    s.finish()
-}
-
 // Import shared git validation
-use crate::shared::git::is_git_repository;
+use crate::shared::git::{get_repository_identity, is_git_repository, open_repository};
 
 /// Gets the root path of a git repository.
 ///
@@ -73,7 +61,7 @@ pub async fn get_root_path(path: String) -> Result<RootPathResponse, AppError> {
         ));
     }
 
-    let repo = Repository::open(raw_path).map_err(|e| {
+    let repo = open_repository(raw_path).map_err(|e| {
         AppError::new(
             format!("Failed to open git repository: {}", e),
             "git_repository_error",
@@ -81,18 +69,16 @@ pub async fn get_root_path(path: String) -> Result<RootPathResponse, AppError> {
         )
     })?;
 
-    let workdir = repo.workdir().ok_or_else(|| {
-        AppError::new(
-            "Repository has no working directory".to_string(),
-            "no_workdir",
-            Some("The git repository is bare".to_string()),
-        )
-    })?;
+    // Bare repositories have no working directory, the git dir is the root
+    let root = repo.workdir().unwrap_or_else(|| repo.path());
 
-    let rootpath = workdir.to_string_lossy().to_string();
+    let rootpath = root.to_string_lossy().to_string();
+    let identity = get_repository_identity(&repo, root);
 
     Ok(RootPathResponse {
-        root_path: rootpath.clone(),
-        id: Some(calculate_hash(&rootpath).to_string()),
+        root_path: rootpath,
+        id: Some(identity.id),
+        root_commit: identity.root_commit,
+        is_bare: repo.is_bare(),
     })
 }
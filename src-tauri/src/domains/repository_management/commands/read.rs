@@ -1,13 +1,25 @@
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+use tauri_specta::Event;
 
+use crate::domains::branch_management::git::branch::BranchListOptions;
+use crate::domains::branch_management::git::head::CurrentBranch;
+use crate::domains::branch_management::git::merge::MergeDetection;
+use crate::domains::repository_management::events::RepositoryDiscoveredEvent;
+use crate::domains::repository_management::services::discovery::RepositoryStatus;
+use crate::domains::repository_management::services::scanning::{ScanOptions, ScannedRepository};
 use crate::shared::error::AppError;
 
 #[derive(Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct GetRepositoryInput {
     pub path: String,
+    /// Branch to measure merged status against, auto-detected when `None`
+    pub base_branch: Option<String>,
+    /// Merge detection mode, `Ancestry` when `None`
+    pub merge_detection: Option<MergeDetection>,
 }
 
 #[derive(Serialize, Deserialize, specta::Type)]
@@ -15,17 +27,44 @@ pub struct GetRepositoryInput {
 pub struct GetRepositoryOutput {
     pub path: String,
     pub branches: Vec<crate::domains::branch_management::git::branch::Branch>,
-    pub current_branch: String,
+    /// What HEAD points to: a branch, a detached commit or an unborn branch
+    pub current_branch: CurrentBranch,
     pub branches_count: u32,
     pub name: String,
     pub id: String,
+    pub root_commit: Option<String>,
+    pub base_branch: Option<String>,
+    pub base_candidates: Vec<String>,
+    /// Why `branches` may be empty
+    pub status: RepositoryStatus,
+    /// Whether the repository is bare, in which case `switch_branch` is unavailable
+    pub is_bare: bool,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRepositoriesInput {
+    /// Workspace folder to scan
+    pub path: String,
+    /// How many folder levels below `path` are visited, 4 when `None`
+    pub max_depth: Option<u32>,
+    /// Glob patterns of folders to skip, `node_modules` is always skipped
+    pub ignore_patterns: Option<Vec<String>>,
+    /// Branch to count merged branches against, auto-detected per repository when `None`
+    pub base_branch: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRepositoriesOutput {
+    pub repositories: Vec<ScannedRepository>,
 }
 
 /// Gets information about a git repository.
 ///
 /// # Arguments
 ///
-/// * `input` - Input parameters containing the repository path
+/// * `input` - Input parameters containing the repository path and listing options
 ///
 /// # Returns
 ///
@@ -34,7 +73,12 @@ pub struct GetRepositoryOutput {
 #[specta::specta]
 pub async fn get_repository(input: GetRepositoryInput) -> Result<GetRepositoryOutput, AppError> {
     let raw_path = Path::new(&input.path);
-    let response = super::super::services::discovery::get_repository(raw_path, &input.path).await?;
+    let options = BranchListOptions {
+        base_branch: input.base_branch,
+        merge_detection: input.merge_detection.unwrap_or_default(),
+    };
+    let response =
+        super::super::services::discovery::get_repository(raw_path, &input.path, options).await?;
 
     Ok(GetRepositoryOutput {
         path: response.path,
@@ -43,5 +87,65 @@ pub async fn get_repository(input: GetRepositoryInput) -> Result<GetRepositoryOu
         branches_count: response.branches_count,
         name: response.name,
         id: response.id,
+        root_commit: response.root_commit,
+        base_branch: response.base_branch,
+        base_candidates: response.base_candidates,
+        status: response.status,
+        is_bare: response.is_bare,
     })
 }
+
+/// Lists the git repositories found in a workspace folder.
+///
+/// A `RepositoryDiscoveredEvent` is emitted for each repository as soon as it
+/// is found, so results can be shown before the scan completes.
+///
+/// # Arguments
+///
+/// * `app` - The AppHandle
+/// * `input` - Input parameters containing the workspace path and scan options
+///
+/// # Returns
+///
+/// * `Result<ListRepositoriesOutput, AppError>` - Every repository found or an error
+#[tauri::command(async)]
+#[specta::specta]
+pub async fn list_repositories(
+    app: tauri::AppHandle,
+    input: ListRepositoriesInput,
+) -> Result<ListRepositoriesOutput, AppError> {
+    let defaults = ScanOptions::default();
+    let options = ScanOptions {
+        max_depth: input.max_depth.unwrap_or(defaults.max_depth),
+        ignore_patterns: input.ignore_patterns.unwrap_or_default(),
+        branch_list_options: BranchListOptions {
+            base_branch: input.base_branch,
+            ..Default::default()
+        },
+    };
+    let workspace_path = input.path;
+
+    // Walking a large workspace blocks, keep it off the async runtime
+    let repositories = tauri::async_runtime::spawn_blocking(move || {
+        let raw_path = Path::new(&workspace_path);
+        super::super::services::scanning::scan_repositories(raw_path, &options, |repository| {
+            let _ = app.emit(
+                RepositoryDiscoveredEvent::NAME,
+                RepositoryDiscoveredEvent {
+                    workspace_path: workspace_path.clone(),
+                    repository: repository.clone(),
+                },
+            );
+        })
+    })
+    .await
+    .map_err(|e| {
+        AppError::new(
+            format!("Failed to scan the workspace: {}", e),
+            "workspace_scan_failed",
+            Some(e.to_string()),
+        )
+    })??;
+
+    Ok(ListRepositoriesOutput { repositories })
+}
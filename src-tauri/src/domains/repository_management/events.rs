@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 use tauri_specta::Event;
 
+use super::services::scanning::ScannedRepository;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct RepositoryLoadedEvent {
@@ -14,6 +16,18 @@ impl Event for RepositoryLoadedEvent {
     const NAME: &'static str = "repository-loaded";
 }
 
+/// Emitted by `list_repositories` for each repository as soon as it is found.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryDiscoveredEvent {
+    pub workspace_path: String,
+    pub repository: ScannedRepository,
+}
+
+impl Event for RepositoryDiscoveredEvent {
+    const NAME: &'static str = "repository-discovered";
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct NotificationEvent {
@@ -1,17 +1,41 @@
 use std::path::Path;
 
-use crate::domains::branch_management::git::branch::Branch;
+use crate::domains::branch_management::git::branch::{Branch, BranchListOptions};
+use crate::domains::branch_management::git::head::CurrentBranch;
+use crate::domains::branch_management::services::protection::{BranchProtection, ProtectionRules};
 use crate::shared::error::AppError;
 
+/// Whether a repository has anything to list, so an empty branch list can be
+/// told apart from a failure.
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum RepositoryStatus {
+    /// The repository has local branches
+    Ready,
+    /// Freshly initialised: HEAD points to a branch that has no commit yet
+    NoCommits,
+    /// Commits exist but no local branch points to them, e.g. all branches
+    /// were deleted while HEAD was detached
+    NoBranches,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct GitDirResponse {
     pub path: String,
     pub branches: Vec<Branch>,
-    pub current_branch: String,
+    pub current_branch: CurrentBranch,
     pub branches_count: u32,
     pub name: String,
+    /// Stable id derived from the canonical root path, same as `get_repository_root`
     pub id: String,
+    /// First commit of the history, to recognise the repository after a move
+    pub root_commit: Option<String>,
+    pub base_branch: Option<String>,
+    pub base_candidates: Vec<String>,
+    pub status: RepositoryStatus,
+    /// Bare repositories can be listed and cleaned but not switched
+    pub is_bare: bool,
 }
 
 /// Get information about a git repository.
@@ -20,11 +44,16 @@ pub struct GitDirResponse {
 ///
 /// * `raw_path` - Path to the git repository
 /// * `path` - Original path string
+/// * `options` - Branch listing options, the base branch is auto-detected when `None`
 ///
 /// # Returns
 ///
 /// * `Result<GitDirResponse, AppError>` - Repository information or an error
-pub async fn get_repository(raw_path: &Path, path: &str) -> Result<GitDirResponse, AppError> {
+pub async fn get_repository(
+    raw_path: &Path,
+    path: &str,
+    options: BranchListOptions,
+) -> Result<GitDirResponse, AppError> {
     // Check if it's a git repository
     if !super::validation::is_git_repository(raw_path)? {
         return Err(AppError::new(
@@ -47,17 +76,31 @@ pub async fn get_repository(raw_path: &Path, path: &str) -> Result<GitDirRespons
     let root_path_response =
         crate::domains::path_operations::service::get_root_path(path.to_string()).await?;
     let root_path = root_path_response.root_path;
+    let is_bare = root_path_response.is_bare;
+    let id = root_path_response.id.unwrap_or_default();
+    let root_commit = root_path_response.root_commit;
 
     let raw_root_path = Path::new(&root_path);
 
+    let base_branches = crate::domains::branch_management::git::base::get_base_branches(
+        raw_root_path,
+        options.base_branch.as_deref(),
+    )?;
+
     // Get branches from branch management domain (vertical slice architecture)
+    let options = BranchListOptions {
+        base_branch: base_branches.selected.clone(),
+        ..options
+    };
     let mut branches =
         crate::domains::branch_management::git::branch::get_all_branches_with_last_commit(
             raw_root_path,
+            &options,
         )?;
+    BranchProtection::load(raw_root_path, ProtectionRules::default())?.apply(&mut branches);
     branches.sort_by(|a, b| b.current.cmp(&a.current));
-    let current =
-        crate::domains::branch_management::git::branch::get_current_branch(raw_root_path)?;
+    let current_branch =
+        crate::domains::branch_management::git::head::get_head_state(raw_root_path)?;
 
     // Extract repository name
     let repo_name = raw_root_path
@@ -80,12 +123,23 @@ pub async fn get_repository(raw_path: &Path, path: &str) -> Result<GitDirRespons
         .to_string();
     let branches_count = branches.len() as u32;
 
+    let status = match (&current_branch, branches.is_empty()) {
+        (_, false) => RepositoryStatus::Ready,
+        (CurrentBranch::Unborn { .. }, true) => RepositoryStatus::NoCommits,
+        (_, true) => RepositoryStatus::NoBranches,
+    };
+
     Ok(GitDirResponse {
         path: root_path,
         branches,
-        current_branch: current.to_string(),
+        current_branch,
         branches_count,
-        name: repo_name.clone(),
-        id: repo_name,
+        name: repo_name,
+        id,
+        root_commit,
+        base_branch: base_branches.selected,
+        base_candidates: base_branches.candidates,
+        status,
+        is_bare,
     })
 }
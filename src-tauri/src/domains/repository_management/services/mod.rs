@@ -1,2 +1,3 @@
 pub mod discovery;
+pub mod scanning;
 pub mod validation;
@@ -0,0 +1,226 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::domains::branch_management::git::branch::{
+    get_all_branches_with_last_commit, BranchListOptions,
+};
+use crate::shared::error::AppError;
+use crate::shared::git::{get_repository_identity, is_git_repository, open_repository};
+use crate::shared::utils::glob::glob_match;
+
+/// Folders that never contain repositories worth cleaning and are too large to walk.
+const ALWAYS_SKIPPED_DIRS: [&str; 2] = ["node_modules", ".git"];
+
+/// A repository found while scanning a workspace.
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScannedRepository {
+    pub path: String,
+    pub name: String,
+    /// Same stable id as `get_repository`
+    pub id: String,
+    pub is_bare: bool,
+    pub branches_count: u32,
+    /// Branches, other than the current one, merged into the base branch
+    pub merged_branches_count: u32,
+    /// Why the branches could not be listed, the counts are zero then
+    pub error: Option<AppError>,
+}
+
+/// Options to scan a workspace for repositories.
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// How many folder levels below the workspace root are visited
+    pub max_depth: u32,
+    /// Glob patterns matched against folder names and paths relative to the root
+    pub ignore_patterns: Vec<String>,
+    pub branch_list_options: BranchListOptions,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            ignore_patterns: Vec::new(),
+            branch_list_options: BranchListOptions::default(),
+        }
+    }
+}
+
+/// Walks a folder tree and reports every repository in it.
+///
+/// Folders inside a repository are not visited, and symbolic links are not
+/// followed. A repository whose branches cannot be listed is still reported,
+/// with its error.
+///
+/// # Arguments
+///
+/// * `root` - The workspace folder to scan
+/// * `options` - Depth limit, ignore patterns and branch listing options
+/// * `on_found` - Called with each repository as soon as it is found
+///
+/// # Returns
+///
+/// * `Result<Vec<ScannedRepository>, AppError>` - Every repository found, or an
+///   error if the workspace folder cannot be read
+pub fn scan_repositories(
+    root: &Path,
+    options: &ScanOptions,
+    mut on_found: impl FnMut(&ScannedRepository),
+) -> Result<Vec<ScannedRepository>, AppError> {
+    if !root.is_dir() {
+        return Err(AppError::new(
+            format!("Unable to access the path: {}", root.display()),
+            "unable_to_access_dir",
+            Some("The workspace folder does not exist or is not a directory".to_string()),
+        ));
+    }
+
+    let mut repositories = Vec::new();
+    let mut pending: Vec<(PathBuf, u32)> = vec![(root.to_path_buf(), 0)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        if looks_like_repository(&dir) && is_git_repository(&dir).unwrap_or(false) {
+            let repository = scan_repository(&dir, &options.branch_list_options);
+            on_found(&repository);
+            repositories.push(repository);
+            continue;
+        }
+
+        if depth >= options.max_depth {
+            continue;
+        }
+
+        // Unreadable folders are skipped rather than failing the whole scan
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        let mut children: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .map(|entry| entry.path())
+            .filter(|path| !is_ignored(root, path, &options.ignore_patterns))
+            .collect();
+
+        // Visit folders in alphabetical order
+        children.sort();
+        pending.extend(children.into_iter().rev().map(|child| (child, depth + 1)));
+    }
+
+    Ok(repositories)
+}
+
+fn scan_repository(root: &Path, options: &BranchListOptions) -> ScannedRepository {
+    let name = root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (id, is_bare) = match open_repository(root) {
+        Ok(repo) => (get_repository_identity(&repo, root).id, repo.is_bare()),
+        Err(_) => (String::new(), false),
+    };
+
+    let mut repository = ScannedRepository {
+        path: root.display().to_string(),
+        name,
+        id,
+        is_bare,
+        branches_count: 0,
+        merged_branches_count: 0,
+        error: None,
+    };
+
+    match get_all_branches_with_last_commit(root, options) {
+        Ok(branches) => {
+            repository.branches_count = branches.len() as u32;
+            repository.merged_branches_count = branches
+                .iter()
+                .filter(|branch| branch.fully_merged && !branch.current)
+                .count() as u32;
+        }
+        Err(e) => repository.error = Some(e),
+    }
+
+    repository
+}
+
+/// Cheap check before opening anything: a working directory with a `.git`
+/// folder or gitfile, or a bare repository.
+fn looks_like_repository(dir: &Path) -> bool {
+    dir.join(".git").exists() || (dir.join("HEAD").is_file() && dir.join("objects").is_dir())
+}
+
+fn is_ignored(root: &Path, path: &Path, ignore_patterns: &[String]) -> bool {
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy(),
+        None => return false,
+    };
+    if ALWAYS_SKIPPED_DIRS.contains(&name.as_ref()) {
+        return true;
+    }
+
+    let relative = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    ignore_patterns
+        .iter()
+        .any(|pattern| glob_match(pattern, &name) || glob_match(pattern, &relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, DirectoryGuard};
+
+    fn init_repo(path: &Path) {
+        fs::create_dir_all(path).unwrap();
+        run_git(path, &["init", "--initial-branch=main"]);
+        run_git(path, &["config", "user.name", "Test User"]);
+        run_git(path, &["config", "user.email", "test@example.com"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Initial commit"]);
+    }
+
+    #[test]
+    fn test_scan_repositories() {
+        let _guard = DirectoryGuard::new();
+        let workspace = tempfile::tempdir().unwrap();
+        let root = workspace.path();
+
+        init_repo(&root.join("api"));
+        run_git(&root.join("api"), &["branch", "merged"]);
+        init_repo(&root.join("org").join("web"));
+        init_repo(&root.join("org").join("too").join("deep").join("repo"));
+        init_repo(&root.join("web").join("node_modules").join("dep"));
+        init_repo(&root.join("archive").join("old"));
+        // Folders inside a repository are not scanned
+        init_repo(&root.join("api").join("vendored"));
+
+        let options = ScanOptions {
+            max_depth: 3,
+            ignore_patterns: vec!["archive".to_string()],
+            ..Default::default()
+        };
+
+        let mut streamed = Vec::new();
+        let repositories =
+            scan_repositories(root, &options, |repo| streamed.push(repo.name.clone())).unwrap();
+
+        let names: Vec<&str> = repositories.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "web"]);
+        assert_eq!(streamed, vec!["api".to_string(), "web".to_string()]);
+
+        let api = &repositories[0];
+        assert_eq!(api.branches_count, 2);
+        assert_eq!(api.merged_branches_count, 1);
+        assert!(api.error.is_none());
+        assert_eq!(api.id.len(), 40);
+
+        let result = scan_repositories(&root.join("missing"), &options, |_| {});
+        assert_eq!(result.unwrap_err().kind, "unable_to_access_dir");
+    }
+}
@@ -14,16 +14,21 @@ pub use domains::repository_management::services::discovery::GitDirResponse;
 
 // Re-export command functions
 pub use domains::branch_management::commands::{
-    delete_branches, is_commit_reachable, restore_branch, restore_branches, switch_branch,
+    delete_branches, delete_branches_in_repositories, delete_worktree, get_commit_details,
+    is_commit_reachable, list_branch_diff_stats, list_gone_branches, list_remote_branches,
+    list_worktrees, lock_branch, plan_delete_branches, prune_worktrees, restore_branch,
+    restore_branches, switch_branch, unlock_branch,
 };
 pub use domains::path_operations::commands::get_repository_root;
-pub use domains::repository_management::commands::get_repository;
+pub use domains::repository_management::commands::{get_repository, list_repositories};
 
 // Re-export events
 pub use domains::branch_management::events::{
     BranchDeletedEvent, BranchRestoredEvent, BranchSwitchedEvent,
 };
-pub use domains::repository_management::events::{NotificationEvent, RepositoryLoadedEvent};
+pub use domains::repository_management::events::{
+    NotificationEvent, RepositoryDiscoveredEvent, RepositoryLoadedEvent,
+};
 
 #[cfg(test)]
 pub use shared::utils::test_utils;
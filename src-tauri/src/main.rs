@@ -9,14 +9,19 @@ pub mod domains;
 pub mod shared;
 
 use domains::branch_management::commands::{
-    delete_branches, is_commit_reachable, restore_branch, restore_branches, switch_branch,
+    delete_branches, delete_branches_in_repositories, delete_worktree, get_commit_details,
+    is_commit_reachable, list_branch_diff_stats, list_gone_branches, list_remote_branches,
+    list_worktrees, lock_branch, plan_delete_branches, prune_worktrees, restore_branch,
+    restore_branches, switch_branch, unlock_branch,
 };
 use domains::branch_management::events::{
     BranchDeletedEvent, BranchRestoredEvent, BranchSwitchedEvent,
 };
 use domains::path_operations::commands::get_repository_root;
-use domains::repository_management::commands::get_repository;
-use domains::repository_management::events::{NotificationEvent, RepositoryLoadedEvent};
+use domains::repository_management::commands::{get_repository, list_repositories};
+use domains::repository_management::events::{
+    NotificationEvent, RepositoryDiscoveredEvent, RepositoryLoadedEvent,
+};
 
 fn main() {
     let _ = fix_path_env::fix();
@@ -25,9 +30,21 @@ fn main() {
         .commands(tauri_specta::collect_commands![
             get_repository_root,
             get_repository,
+            list_repositories,
             switch_branch,
+            lock_branch,
+            unlock_branch,
             delete_branches,
+            plan_delete_branches,
+            delete_branches_in_repositories,
             is_commit_reachable,
+            get_commit_details,
+            list_remote_branches,
+            list_gone_branches,
+            list_branch_diff_stats,
+            list_worktrees,
+            delete_worktree,
+            prune_worktrees,
             restore_branch,
             restore_branches
         ])
@@ -36,6 +53,7 @@ fn main() {
             BranchRestoredEvent,
             BranchSwitchedEvent,
             RepositoryLoadedEvent,
+            RepositoryDiscoveredEvent,
             NotificationEvent
         ]);
 
@@ -81,9 +99,21 @@ mod tests {
 
         // Test that we can access the command functions
         let _ = repo_commands::get_repository;
+        let _ = repo_commands::list_repositories;
         let _ = commands::switch_branch;
+        let _ = commands::lock_branch;
+        let _ = commands::unlock_branch;
         let _ = commands::delete_branches;
+        let _ = commands::plan_delete_branches;
+        let _ = commands::delete_branches_in_repositories;
         let _ = commands::is_commit_reachable;
+        let _ = commands::get_commit_details;
+        let _ = commands::list_remote_branches;
+        let _ = commands::list_gone_branches;
+        let _ = commands::list_branch_diff_stats;
+        let _ = commands::list_worktrees;
+        let _ = commands::delete_worktree;
+        let _ = commands::prune_worktrees;
         let _ = commands::restore_branch;
         let _ = commands::restore_branches;
         let _ = path_commands::get_repository_root;
@@ -1,4 +1,4 @@
-#[derive(serde::Serialize, specta::Type, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
 pub struct AppError {
     pub message: String,
     pub kind: String,
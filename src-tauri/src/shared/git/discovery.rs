@@ -0,0 +1,47 @@
+use git2::{Repository, RepositoryOpenFlags};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Opens the repository containing `path`, like git does from a shell.
+///
+/// Parent directories are searched up to the ceilings listed in
+/// `GIT_CEILING_DIRECTORIES`, and `.git` gitfiles (submodules, linked
+/// worktrees) as well as git dirs opened directly are followed, so any folder
+/// inside a repository resolves to that repository.
+///
+/// # Arguments
+///
+/// * `path` - A path inside the repository, its git dir, or its root
+///
+/// # Returns
+///
+/// * `Result<Repository, git2::Error>` - The repository, or a `NotFound` error
+///   when no repository contains `path`
+pub fn open_repository(path: &Path) -> Result<Repository, git2::Error> {
+    let ceilings: Vec<PathBuf> = env::var_os("GIT_CEILING_DIRECTORIES")
+        .map(|dirs| {
+            env::split_paths(&dirs)
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    open_repository_with_ceilings(path, &ceilings)
+}
+
+/// Opens the repository containing `path`, never searching above `ceilings`.
+///
+/// # Arguments
+///
+/// * `path` - A path inside the repository, its git dir, or its root
+/// * `ceilings` - Directories the search for a repository stops at
+///
+/// # Returns
+///
+/// * `Result<Repository, git2::Error>` - The repository or an error
+pub fn open_repository_with_ceilings(
+    path: &Path,
+    ceilings: &[PathBuf],
+) -> Result<Repository, git2::Error> {
+    Repository::open_ext(path, RepositoryOpenFlags::empty(), ceilings)
+}
@@ -0,0 +1,94 @@
+use git2::{ObjectType, Oid, Repository, Sort};
+use std::path::Path;
+
+/// Identity of a repository that stays the same across app upgrades.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryIdentity {
+    /// SHA-1 of the canonical root path, stable as long as the repository is
+    /// not moved
+    pub id: String,
+    /// First commit of the history of HEAD, which survives moves but is
+    /// shared by every clone of the same project
+    pub root_commit: Option<String>,
+}
+
+/// Computes the identity of a repository from its root path and history.
+///
+/// # Arguments
+///
+/// * `repo` - The git repository
+/// * `root` - Root of the repository: its working directory, or git dir when bare
+///
+/// # Returns
+///
+/// * `RepositoryIdentity` - The stable id and root commit of the repository
+pub fn get_repository_identity(repo: &Repository, root: &Path) -> RepositoryIdentity {
+    // Symlinks and relative segments must not give one repository two ids
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let canonical_root = canonical_root.to_string_lossy();
+    let canonical_root = canonical_root.trim_end_matches(['/', '\\']);
+
+    // Git's own object hashing is fixed by its format, unlike `DefaultHasher`
+    let id = Oid::hash_object(ObjectType::Blob, canonical_root.as_bytes())
+        .map(|oid| oid.to_string())
+        .unwrap_or_else(|_| canonical_root.to_string());
+
+    RepositoryIdentity {
+        id,
+        root_commit: find_root_commit(repo).map(|oid| oid.to_string()),
+    }
+}
+
+/// Follows the first parents of HEAD down to the commit without parents,
+/// `None` for an unborn HEAD.
+fn find_root_commit(repo: &Repository) -> Option<Oid> {
+    let mut revwalk = repo.revwalk().ok()?;
+    revwalk.push_head().ok()?;
+    revwalk.simplify_first_parent().ok()?;
+    revwalk.set_sorting(Sort::NONE).ok()?;
+
+    revwalk.filter_map(Result::ok).last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, setup_test_repo, DirectoryGuard};
+
+    #[test]
+    fn test_get_repository_identity() {
+        let _guard = DirectoryGuard::new();
+        let dir = setup_test_repo();
+        let path = dir.path();
+        let root_commit = run_git(path, &["rev-parse", "HEAD"]);
+        run_git(path, &["commit", "--allow-empty", "-m", "Second"]);
+
+        let repo = Repository::open(path).unwrap();
+        let identity = get_repository_identity(&repo, path);
+        assert_eq!(identity.id.len(), 40);
+        assert_eq!(identity.root_commit, Some(root_commit));
+
+        // Trailing separators and non-canonical spellings map to the same id
+        let with_slash = format!("{}/", path.display());
+        assert_eq!(
+            get_repository_identity(&repo, Path::new(&with_slash)).id,
+            identity.id
+        );
+        assert_eq!(
+            get_repository_identity(&repo, &path.join("..").join(path.file_name().unwrap())).id,
+            identity.id
+        );
+
+        // Same project elsewhere: same root commit, different id
+        let clone_dir = tempfile::tempdir().unwrap();
+        run_git(
+            clone_dir.path(),
+            &["clone", path.to_str().unwrap(), "clone"],
+        );
+        let clone_path = clone_dir.path().join("clone");
+        let clone = Repository::open(&clone_path).unwrap();
+        let clone_identity = get_repository_identity(&clone, &clone_path);
+        assert_ne!(clone_identity.id, identity.id);
+        assert_eq!(clone_identity.root_commit, identity.root_commit);
+    }
+}
@@ -1,3 +1,7 @@
+pub mod discovery;
+pub mod identity;
 pub mod validation;
 
+pub use discovery::*;
+pub use identity::*;
 pub use validation::*;
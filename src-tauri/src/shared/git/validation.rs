@@ -1,9 +1,10 @@
 use git2::Repository;
 use std::path::Path;
 
+use super::discovery::open_repository;
 use crate::shared::error::AppError;
 
-/// Checks if the given path is a git repository.
+/// Checks if the given path is inside a git repository.
 ///
 /// # Arguments
 ///
@@ -13,7 +14,7 @@ use crate::shared::error::AppError;
 ///
 /// * `Result<bool, AppError>` - true if it's a git repository, or an error
 pub fn is_git_repository(path: &Path) -> Result<bool, AppError> {
-    match Repository::open(path) {
+    match open_repository(path) {
         Ok(repo) => validate_repository(&repo, path),
         Err(e) => {
             if e.code() == git2::ErrorCode::NotFound {
@@ -48,8 +49,9 @@ fn validate_repository(repo: &Repository, path: &Path) -> Result<bool, AppError>
     match repo.head() {
         Ok(_) => Ok(true),
         Err(e) => {
-            // If the repository is bare, it's still valid
-            if repo.is_bare() {
+            // A bare repository, or a freshly initialised one whose HEAD
+            // points to a branch without commits, is still valid
+            if repo.is_bare() || e.code() == git2::ErrorCode::UnbornBranch {
                 Ok(true)
             } else {
                 Err(AppError::new(
@@ -65,3 +67,20 @@ fn validate_repository(repo: &Repository, path: &Path) -> Result<bool, AppError>
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::test_utils::{run_git, DirectoryGuard};
+
+    #[test]
+    fn test_is_git_repository_with_unborn_head() {
+        let _guard = DirectoryGuard::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path();
+        assert!(!is_git_repository(path).unwrap());
+
+        run_git(path, &["init", "--initial-branch=main"]);
+        assert!(is_git_repository(path).unwrap());
+    }
+}
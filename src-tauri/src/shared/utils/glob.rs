@@ -0,0 +1,77 @@
+/// Matches a text against a glob pattern.
+///
+/// `*` matches any run of characters except `/`, `**` also crosses `/`, and
+/// `?` matches one character other than `/`. Everything else is literal.
+///
+/// # Arguments
+///
+/// * `pattern` - The glob pattern, e.g. `release/*` or `**/vendor`
+/// * `text` - The text to match, e.g. a branch name or a relative path
+///
+/// # Returns
+///
+/// * `bool` - Whether the whole text matches the pattern
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    match_from(&pattern, &text)
+}
+
+fn match_from(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => {
+            let crosses_slash = pattern.get(1) == Some(&'*');
+            let rest = if crosses_slash {
+                &pattern[2..]
+            } else {
+                &pattern[1..]
+            };
+            // `**/` also matches nothing at all, so `**/vendor` matches `vendor`
+            if crosses_slash && rest.first() == Some(&'/') && match_from(&rest[1..], text) {
+                return true;
+            }
+
+            for skip in 0..=text.len() {
+                if match_from(rest, &text[skip..]) {
+                    return true;
+                }
+                if skip < text.len() && !crosses_slash && text[skip] == '/' {
+                    return false;
+                }
+            }
+            false
+        }
+        Some('?') => match text.first() {
+            Some(c) if *c != '/' => match_from(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+        Some(p) => match text.first() {
+            Some(c) if c == p => match_from(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("main", "main"));
+        assert!(!glob_match("main", "main2"));
+
+        assert!(glob_match("release/*", "release/1.0"));
+        assert!(!glob_match("release/*", "release/1.0/hotfix"));
+        assert!(!glob_match("release/*", "feature/release"));
+
+        assert!(glob_match("release/**", "release/1.0/hotfix"));
+        assert!(glob_match("**/vendor", "vendor"));
+        assert!(glob_match("**/vendor", "libs/third-party/vendor"));
+
+        assert!(glob_match("v?.x", "v1.x"));
+        assert!(!glob_match("v?.x", "v10.x"));
+        assert!(glob_match("*.tmp", ".tmp"));
+    }
+}
@@ -177,6 +177,26 @@ fn setup_test_repo_with_git_command(git: &dyn GitCommand) -> tempfile::TempDir {
     dir
 }
 
+/// Runs a git command in the given directory and returns its trimmed stdout.
+///
+/// Panics with git's stderr when the command fails, which keeps repository
+/// setup steps in tests short.
+#[cfg(test)]
+pub fn run_git(path: &std::path::Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(path)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
 /// Saves the current working directory and returns a struct to manage it.
 ///
 /// This is useful in tests to ensure that changing directories in one test
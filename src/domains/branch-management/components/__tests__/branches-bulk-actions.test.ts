@@ -26,7 +26,7 @@ vi.mock('../components/delete-branch-modal.svelte', () => {
 
 const mockRepo: Repository = {
 	name: 'test-repo',
-	currentBranch: 'main',
+	currentBranch: { type: 'Branch', name: 'main' },
 	path: '/path/to/repo',
 	branchesCount: 3,
 	id: '1',
@@ -84,7 +84,7 @@ describe('BranchesBulkActions Component', () => {
 	};
 
 	beforeEach(() => {
-		const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+		const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 		search?.clear();
 		vi.clearAllMocks();
 	});
@@ -158,7 +158,7 @@ describe('BranchesBulkActions Component', () => {
 				selectedSearchLength: 1
 			};
 
-			const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+			const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 			search?.set('feature');
 
 			const { getByTestId } = render(TestWrapper, {
@@ -220,7 +220,7 @@ describe('BranchesBulkActions Component', () => {
 		});
 
 		test('search input correctly updates the search store', async () => {
-			const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+			const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 			search?.clear();
 
 			const { getByTestId } = render(TestWrapper, {
@@ -234,7 +234,7 @@ describe('BranchesBulkActions Component', () => {
 		});
 
 		test('search is correctly initialized with existing search query', () => {
-			const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+			const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 			search?.set('existing-query');
 
 			const { getByTestId } = render(TestWrapper, {
@@ -246,7 +246,7 @@ describe('BranchesBulkActions Component', () => {
 		});
 
 		test('handles case when search state is undefined', () => {
-			const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+			const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 			// @ts-expect-error - intentionally setting to undefined to test the condition
 			search.state = undefined;
 
@@ -287,7 +287,7 @@ describe('BranchesBulkActions Component', () => {
 		});
 
 		test('does not select the current branch when selecting all', async () => {
-			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.name);
+			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.id);
 			selectedStore?.clear();
 
 			const { getByTestId } = render(TestWrapper, {
@@ -305,10 +305,10 @@ describe('BranchesBulkActions Component', () => {
 		});
 
 		test('does not select locked branches when selecting all', async () => {
-			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.name);
+			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.id);
 			selectedStore?.clear();
 
-			const lockedStore = getLockedBranchesStore(defaultProps?.currentRepo.name);
+			const lockedStore = getLockedBranchesStore(defaultProps?.currentRepo.id);
 			lockedStore?.add(['feature-1']);
 
 			const { getByTestId } = render(TestWrapper, {
@@ -332,7 +332,7 @@ describe('BranchesBulkActions Component', () => {
 				selectedSearchLength: 2
 			};
 
-			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.name);
+			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.id);
 			selectedStore?.add(['feature-1', 'feature-2']);
 
 			const { getByTestId } = render(TestWrapper, {
@@ -348,7 +348,7 @@ describe('BranchesBulkActions Component', () => {
 		});
 
 		test('select all checkbox is not checked when no branches are selected', () => {
-			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.name);
+			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.id);
 			selectedStore?.clear();
 
 			const { getByTestId } = render(TestWrapper, {
@@ -379,7 +379,7 @@ describe('BranchesBulkActions Component', () => {
 	describe('Text and Pluralization', () => {
 		test('displays correct singular form when selectibleCount is 1', () => {
 			// Clear search state to ensure we show selectible-count-info
-			const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+			const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 			search?.clear();
 
 			const props = { ...defaultProps, selectibleCount: 1, selectedSearchLength: 0 };
@@ -391,7 +391,7 @@ describe('BranchesBulkActions Component', () => {
 
 		test('displays correct plural form when selectibleCount is greater than 1', () => {
 			// Clear search state to ensure we show selectible-count-info
-			const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+			const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 			search?.clear();
 
 			const props = { ...defaultProps, selectibleCount: 2, selectedSearchLength: 0 };
@@ -408,7 +408,7 @@ describe('BranchesBulkActions Component', () => {
 				selectedSearchLength: 1
 			};
 
-			const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+			const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 			search?.set('feature');
 
 			const { getByTestId } = render(TestWrapper, {
@@ -425,10 +425,10 @@ describe('BranchesBulkActions Component', () => {
 				selectedSearchLength: 2
 			};
 
-			const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+			const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 			search?.set('feature');
 
-			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.name);
+			const selectedStore = getSelectedBranchesStore(defaultProps?.currentRepo.id);
 			selectedStore?.add(['feature-1', 'feature-2']);
 
 			const { getByTestId } = render(TestWrapper, {
@@ -443,7 +443,7 @@ describe('BranchesBulkActions Component', () => {
 		test('renders correctly when selectibleCount is 1 and there is a search query', () => {
 			const props = { ...defaultProps, selectibleCount: 1, selectedSearchLength: 1 };
 
-			const search = getSearchBranchesStore(defaultProps?.currentRepo.name);
+			const search = getSearchBranchesStore(defaultProps?.currentRepo.id);
 			search?.set('feature');
 
 			const { getByTestId } = render(TestWrapper, {
@@ -84,7 +84,7 @@ describe('DeleteBranchModal Component', () => {
 		const repository = getRepositoryStore('test-repo');
 		repository?.set({
 			name: 'test-repo',
-			currentBranch: 'main',
+			currentBranch: { type: 'Branch', name: 'main' },
 			path: '/path/to/repo',
 			branchesCount: 3,
 			id: '1',
@@ -187,7 +187,12 @@ describe('DeleteBranchModal Component', () => {
 			// First argument should be the branches/path object
 			expect(callArgs[0]).toEqual({
 				path: '/path/to/repo',
-				branches: ['feature-1']
+				branches: ['feature-1'],
+				mode: null,
+				atomic: null,
+				protection: null,
+				baseBranch: null,
+				mergeDetection: null
 			});
 
 			// Second argument should have onSuccess function
@@ -220,7 +225,12 @@ describe('DeleteBranchModal Component', () => {
 			// First argument should be the branches/path object
 			expect(callArgs[0]).toEqual({
 				path: '/path/to/repo',
-				branches: ['feature-1', 'feature-2']
+				branches: ['feature-1', 'feature-2'],
+				mode: null,
+				atomic: null,
+				protection: null,
+				baseBranch: null,
+				mergeDetection: null
 			});
 
 			// Second argument should have onSuccess function
@@ -258,7 +268,12 @@ describe('DeleteBranchModal Component', () => {
 			// First argument should be the branches/path object
 			expect(callArgs[0]).toEqual({
 				path: '/path/to/repo',
-				branches: ['main']
+				branches: ['main'],
+				mode: null,
+				atomic: null,
+				protection: null,
+				baseBranch: null,
+				mergeDetection: null
 			});
 
 			// Second argument should have onSuccess function
@@ -302,7 +317,8 @@ describe('DeleteBranchModal Component', () => {
 							}
 						}
 					}
-				]
+				],
+				results: [{ branch: 'feature-1', error: null }]
 			};
 
 			// Execute the mutation's onSuccess callback directly
@@ -362,6 +378,10 @@ describe('DeleteBranchModal Component', () => {
 							}
 						}
 					}
+				],
+				results: [
+					{ branch: 'feature-1', error: null },
+					{ branch: 'feature-2', error: null }
 				]
 			};
 
@@ -375,6 +395,64 @@ describe('DeleteBranchModal Component', () => {
 				message: '- **feature-1** (was abc123)\n\n- **feature-2** (was def456)'
 			});
 		});
+
+		test('reports refused branches and keeps them selected', async () => {
+			const { notifications } = await import('$domains/notifications/store/notifications.svelte');
+			const selectedBranches = getSelectedBranchesStore('test-repo');
+			selectedBranches?.clear();
+			selectedBranches?.add(['feature-1', 'feature-2']);
+
+			(createDeleteBranchesMutation as Mock).mockReturnValue({
+				mutate: vi.fn(),
+				isPending: false
+			});
+
+			const { getByTestId } = render(TestWrapper, {
+				props: { component: DeleteBranchModal, props: { id: 'test-repo' } }
+			});
+
+			await fireEvent.click(getByTestId('open-dialog-button'));
+			await fireEvent.click(getByTestId('delete-button'));
+
+			const mutationConfig = (createDeleteBranchesMutation as Mock).mock.calls[0][0];
+
+			// feature-2 is refused, feature-1 is deleted
+			mutationConfig.onSuccess({
+				deletedBranches: [
+					{
+						branch: {
+							name: 'feature-1',
+							lastCommit: {
+								shortSha: 'abc123'
+							}
+						}
+					}
+				],
+				results: [
+					{ branch: 'feature-1', error: null },
+					{
+						branch: 'feature-2',
+						error: {
+							message: 'Branch **feature-2** is not fully merged',
+							kind: 'branch_not_fully_merged',
+							description: null
+						}
+					}
+				]
+			});
+
+			expect(notifications.push).toHaveBeenCalledWith({
+				feedback: 'success',
+				title: 'Branch deleted from test-repo repository',
+				message: '- **feature-1** (was abc123)'
+			});
+			expect(notifications.push).toHaveBeenCalledWith({
+				feedback: 'danger',
+				title: 'Branch not deleted from test-repo repository',
+				message: '- Branch **feature-2** is not fully merged'
+			});
+			expect(selectedBranches?.list).toEqual(['feature-2']);
+		});
 	});
 
 	describe('Branch Sorting', () => {
@@ -437,7 +515,8 @@ describe('DeleteBranchModal Component', () => {
 							fullyMerged: false
 						}
 					}
-				]
+				],
+				results: [{ branch: 'feature-1', error: null }]
 			};
 
 			// Execute the handleDelete onSuccess callback
@@ -101,7 +101,7 @@ describe('Navigation Integration', () => {
 				name: 'Test Repo',
 				path: '/path/to/repo',
 				branches: [],
-				currentBranch: 'main',
+				currentBranch: { type: 'Branch', name: 'main' },
 				branchesCount: 0
 			}
 		});
@@ -161,7 +161,7 @@ describe('AddButton', () => {
 				name: 'Existing Repo',
 				path: '/path/to/existing/repo',
 				branches: [],
-				currentBranch: '',
+				currentBranch: { type: 'Branch', name: 'main' },
 				branchesCount: 0
 			};
 
@@ -239,7 +239,7 @@ describe('AddButton', () => {
 
 			// Since goto is called inside the effect after repository check, we need to manually mock it
 			// This simulates the navigation that would happen in the component
-			setTimeout(() => goto(resolve(`/repos/${mockRepo.name}`)), 100);
+			setTimeout(() => goto(resolve(`/repos/${mockRepo.id}`)), 100);
 
 			const { getByRole } = render(TestWrapper, {
 				props: { component: AddButton, props: { visuallyHiddenLabel: false } }
@@ -250,7 +250,7 @@ describe('AddButton', () => {
 
 			await waitFor(
 				() => {
-					expect(goto).toHaveBeenCalledWith(`/repos/${mockRepo.name}`);
+					expect(goto).toHaveBeenCalledWith(`/repos/${mockRepo.id}`);
 				},
 				{ timeout: 3000 }
 			);
@@ -306,7 +306,7 @@ describe('AddButton', () => {
 				name: 'New Repo',
 				path: '/path/to/new/repo',
 				branches: [],
-				currentBranch: '',
+				currentBranch: { type: 'Branch', name: 'main' },
 				branchesCount: 0
 			};
 
@@ -423,7 +423,7 @@ describe('AddButton', () => {
 				name: 'Test Repo',
 				path: '/test/directory',
 				branches: [],
-				currentBranch: '',
+				currentBranch: { type: 'Branch', name: 'main' },
 				branchesCount: 0
 			};
 
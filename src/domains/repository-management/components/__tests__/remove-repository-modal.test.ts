@@ -12,7 +12,7 @@ const mockRepository: Repository = {
 	name: 'test-repo',
 	path: '/path/to/test-repo',
 	branches: [],
-	currentBranch: 'main',
+	currentBranch: { type: 'Branch', name: 'main' },
 	branchesCount: 0,
 	id: '1'
 };
@@ -21,7 +21,7 @@ const mockRepository2: Repository = {
 	name: 'test-repo-2',
 	path: '/path/to/test-repo-2',
 	branches: [],
-	currentBranch: 'main',
+	currentBranch: { type: 'Branch', name: 'main' },
 	branchesCount: 0,
 	id: '2'
 };
@@ -52,14 +52,14 @@ vi.mock('$domains/branch-management/store/search-branches.svelte', () => ({
 
 describe('RemoveRepositoryModal', () => {
 	beforeEach(() => {
-		getSearchBranchesStore(mockRepository?.name);
-		getRepositoryStore(mockRepository?.name);
-		const repository = getRepositoryStore(mockRepository?.name);
+		getSearchBranchesStore(mockRepository?.id);
+		getRepositoryStore(mockRepository?.id);
+		const repository = getRepositoryStore(mockRepository?.id);
 		repository?.set(mockRepository);
 
-		getSearchBranchesStore(mockRepository2?.name);
-		getRepositoryStore(mockRepository2?.name);
-		const repository2 = getRepositoryStore(mockRepository2?.name);
+		getSearchBranchesStore(mockRepository2?.id);
+		getRepositoryStore(mockRepository2?.id);
+		const repository2 = getRepositoryStore(mockRepository2?.id);
 		repository2?.set(mockRepository2);
 
 		// Reset mocks between tests
@@ -133,9 +133,9 @@ describe('RemoveRepositoryModal', () => {
 			const removeButton = getByTestId('confirm-remove');
 			await fireEvent.click(removeButton);
 
-			const repository = getRepositoryStore(mockRepository?.name);
+			const repository = getRepositoryStore(mockRepository?.id);
 
-			expect(RepositoryStore.repositories?.has(mockRepository.name)).toBeFalsy();
+			expect(RepositoryStore.repositories?.has(mockRepository.id)).toBeFalsy();
 			expect(repository?.state).toBeUndefined();
 		});
 
@@ -182,16 +182,16 @@ describe('RemoveRepositoryModal', () => {
 			RepositoryStore.repositories?.clear();
 
 			// Add both repositories to the store
-			const firstRepo = getRepositoryStore(mockRepository?.name);
+			const firstRepo = getRepositoryStore(mockRepository?.id);
 			firstRepo?.set(mockRepository);
 
-			const secondRepo = getRepositoryStore(mockRepository2?.name);
+			const secondRepo = getRepositoryStore(mockRepository2?.id);
 			secondRepo?.set(mockRepository2);
 
 			// Verify setup
 			expect(RepositoryStore.repositories?.list).toHaveLength(2);
-			expect(RepositoryStore.repositories?.has(mockRepository?.name)).toBeTruthy();
-			expect(RepositoryStore.repositories?.has(mockRepository2?.name)).toBeTruthy();
+			expect(RepositoryStore.repositories?.has(mockRepository?.id)).toBeTruthy();
+			expect(RepositoryStore.repositories?.has(mockRepository2?.id)).toBeTruthy();
 
 			const { getByTestId } = render(TestWrapper, {
 				props: testWrapperWithProps(RemoveRepositoryModal, { currentRepo: mockRepository })
@@ -204,7 +204,7 @@ describe('RemoveRepositoryModal', () => {
 			await fireEvent.click(removeButton);
 
 			// After removing the first repository, it should navigate to the second one
-			expect(goto).toHaveBeenCalledWith('/repos/test-repo-2');
+			expect(goto).toHaveBeenCalledWith('/repos/2');
 		});
 
 		test('should navigate to add-first when no repositories remain', async () => {
@@ -215,12 +215,12 @@ describe('RemoveRepositoryModal', () => {
 			RepositoryStore.repositories?.clear();
 
 			// Set up only the test repository
-			const store = getRepositoryStore(mockRepository?.name);
+			const store = getRepositoryStore(mockRepository?.id);
 			store?.set(mockRepository);
 
 			// Verify setup
 			expect(RepositoryStore.repositories?.list).toHaveLength(1);
-			expect(RepositoryStore.repositories?.has(mockRepository2?.name)).toBeFalsy();
+			expect(RepositoryStore.repositories?.has(mockRepository2?.id)).toBeFalsy();
 
 			const { getByTestId } = render(TestWrapper, {
 				props: testWrapperWithProps(RemoveRepositoryModal, { currentRepo: mockRepository })
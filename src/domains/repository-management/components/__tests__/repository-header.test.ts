@@ -45,7 +45,7 @@ describe('RepositoryHeader', () => {
 		name: 'Test-Repo',
 		path: '/path/to/repo',
 		branches: mockBranches,
-		currentBranch: 'main',
+		currentBranch: { type: 'Branch', name: 'main' },
 		branchesCount: 0
 	};
 
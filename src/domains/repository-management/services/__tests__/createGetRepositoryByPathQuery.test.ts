@@ -32,7 +32,7 @@ describe('createGetRepositoryQuery', () => {
 	const mockRepository = mockDataFactory.repository({
 		path: '/path/to/repository',
 		name: 'test-repo',
-		currentBranch: 'main',
+		currentBranch: { type: 'Branch', name: 'main' },
 		branchesCount: 1,
 		id: '1',
 		branches: [
@@ -58,7 +58,7 @@ describe('createGetRepositoryQuery', () => {
 		const mockCommand = vi.mocked(commands.getRepository);
 		mockCommand.mockResolvedValue({
 			status: 'ok',
-			data: mockRepository
+			data: mockRepository as unknown as GetRepositoryOutput
 		});
 
 		// Mock repository store
@@ -114,7 +114,11 @@ describe('createGetRepositoryQuery', () => {
 		// Call the query function directly
 		await queryFn();
 
-		expect(vi.mocked(commands.getRepository)).toHaveBeenCalledWith({ path: mockPath });
+		expect(vi.mocked(commands.getRepository)).toHaveBeenCalledWith({
+			path: mockPath,
+			baseBranch: null,
+			mergeDetection: null
+		});
 	});
 
 	it('should return the repository from the query function', async () => {
@@ -196,7 +200,7 @@ describe('createGetRepositoryQuery', () => {
 				}
 			],
 			id: '1',
-			currentBranch: 'main',
+			currentBranch: { type: 'Branch', name: 'main' },
 			branchesCount: 1,
 			name: 'test-repo'
 			// This is still malformed due to missing Branch fields, but has the required top-level fields
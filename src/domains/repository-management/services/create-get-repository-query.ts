@@ -8,7 +8,7 @@ export function createGetRepositoryQuery(
 ) {
 	return createTauriQuery('getRepository', {
 		queryKey: ['branches', 'get-all', path() ?? ''],
-		input: () => ({ path: path() ?? '' }),
+		input: () => ({ path: path() ?? '', baseBranch: null, mergeDetection: null }),
 		select: (data) => {
 			// Create the repository with processed branches and branch count
 			const repository: Repository = {
@@ -17,7 +17,7 @@ export function createGetRepositoryQuery(
 			};
 
 			// Update the repository store with the processed data
-			const repositoryStore = getRepositoryStore(repository.name);
+			const repositoryStore = getRepositoryStore(repository.id);
 			if (repositoryStore) {
 				repositoryStore.set(repository);
 			}
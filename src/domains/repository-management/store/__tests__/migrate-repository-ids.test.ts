@@ -0,0 +1,134 @@
+import { describe, it, expect, beforeEach, vi } from 'vitest';
+import { migrateRepositoryIds } from '../migrate-repository-ids';
+import { commands } from '$lib/bindings';
+import { mockDataFactory } from '$utils/test-utils';
+
+vi.mock('$lib/bindings', () => ({
+	commands: {
+		getRepositoryRoot: vi.fn()
+	}
+}));
+
+const getRepositoryRoot = vi.mocked(commands.getRepositoryRoot);
+
+function save(key: string, value: unknown) {
+	localStorage.setItem(key, JSON.stringify(value));
+}
+
+function read(key: string) {
+	const value = localStorage.getItem(key);
+	return value === null ? null : JSON.parse(value);
+}
+
+// Resolves the given paths as repositories, any other path is gone
+function resolvePaths(roots: Record<string, { id: string; rootCommit: string }>) {
+	getRepositoryRoot.mockImplementation(async ({ path }) => {
+		const root = roots[path];
+		return root
+			? { status: 'ok', data: { rootPath: path, ...root } }
+			: { status: 'error', error: { message: 'Not a repository', kind: 'NotFound' } as never };
+	});
+}
+
+describe('migrateRepositoryIds', () => {
+	beforeEach(() => {
+		localStorage.clear();
+		vi.clearAllMocks();
+	});
+
+	it('moves a repository saved under its name to the id of its path', async () => {
+		const { rootCommit, ...repository } = mockDataFactory.repository({
+			id: 'test-repo',
+			path: '/test/repo'
+		});
+		expect(rootCommit).toBeUndefined();
+		save('repositories_list', ['test-repo']);
+		save('store_repository_test-repo', repository);
+		save('store_deleted_branches_test-repo', { branches: [mockDataFactory.branch()] });
+		save('store_locked_test-repo', ['main']);
+		resolvePaths({ '/test/repo': { id: 'sha-id', rootCommit: 'root-sha' } });
+
+		expect(await migrateRepositoryIds()).toBe(true);
+
+		expect(read('repositories_list')).toEqual(['sha-id']);
+		expect(read('store_repository_test-repo')).toBeNull();
+		expect(read('store_repository_sha-id')).toMatchObject({
+			id: 'sha-id',
+			path: '/test/repo',
+			rootCommit: 'root-sha'
+		});
+		expect(read('store_deleted_branches_sha-id')?.branches).toHaveLength(1);
+		expect(read('store_deleted_branches_test-repo')).toBeNull();
+		expect(read('store_locked_sha-id')).toEqual(['main']);
+		expect(read('store_locked_test-repo')).toBeNull();
+	});
+
+	it('keeps a repository saved under its name when its path is gone', async () => {
+		const repository = mockDataFactory.repository({ id: 'test-repo', path: '/gone' });
+		save('repositories_list', ['test-repo']);
+		save('store_repository_test-repo', repository);
+		resolvePaths({});
+
+		expect(await migrateRepositoryIds()).toBe(false);
+
+		expect(read('repositories_list')).toEqual(['test-repo']);
+		expect(read('store_repository_test-repo')).toEqual(repository);
+	});
+
+	it('leaves repositories already saved under their id alone', async () => {
+		save('repositories_list', ['sha-id']);
+		save(
+			'store_repository_sha-id',
+			mockDataFactory.repository({ id: 'sha-id', rootCommit: 'root-sha' })
+		);
+		resolvePaths({ '/test/repo': { id: 'sha-id', rootCommit: 'root-sha' } });
+
+		expect(await migrateRepositoryIds()).toBe(false);
+		expect(read('repositories_list')).toEqual(['sha-id']);
+	});
+
+	it('hands the stores of a moved repository to the one sharing its root commit', async () => {
+		save('repositories_list', ['old-id', 'new-id']);
+		save(
+			'store_repository_old-id',
+			mockDataFactory.repository({ id: 'old-id', path: '/old/repo', rootCommit: 'root-sha' })
+		);
+		save(
+			'store_repository_new-id',
+			mockDataFactory.repository({ id: 'new-id', path: '/new/repo', rootCommit: 'root-sha' })
+		);
+		save('store_deleted_branches_old-id', { branches: [mockDataFactory.branch()] });
+		save('store_deleted_branches_new-id', { branches: [] });
+		resolvePaths({ '/new/repo': { id: 'new-id', rootCommit: 'root-sha' } });
+
+		expect(await migrateRepositoryIds()).toBe(true);
+
+		expect(read('repositories_list')).toEqual(['new-id']);
+		expect(read('store_repository_old-id')).toBeNull();
+		expect(read('store_deleted_branches_new-id')?.branches).toHaveLength(1);
+		expect(read('store_deleted_branches_old-id')).toBeNull();
+	});
+
+	it('does not pick between several clones sharing a root commit', async () => {
+		save('repositories_list', ['old-id', 'clone-a', 'clone-b']);
+		save(
+			'store_repository_old-id',
+			mockDataFactory.repository({ id: 'old-id', path: '/old/repo', rootCommit: 'root-sha' })
+		);
+		save(
+			'store_repository_clone-a',
+			mockDataFactory.repository({ id: 'clone-a', path: '/clone/a', rootCommit: 'root-sha' })
+		);
+		save(
+			'store_repository_clone-b',
+			mockDataFactory.repository({ id: 'clone-b', path: '/clone/b', rootCommit: 'root-sha' })
+		);
+		resolvePaths({
+			'/clone/a': { id: 'clone-a', rootCommit: 'root-sha' },
+			'/clone/b': { id: 'clone-b', rootCommit: 'root-sha' }
+		});
+
+		expect(await migrateRepositoryIds()).toBe(false);
+		expect(read('repositories_list')).toEqual(['old-id', 'clone-a', 'clone-b']);
+	});
+});
@@ -0,0 +1,159 @@
+import { z } from 'zod/v4';
+import { commands, type GetRepositoryRootOutput } from '$lib/bindings';
+import { RepositorySchema, type Repository } from '$services/common';
+import { getLocalStorage } from '$utils/get-local-storage';
+import { getValidatedLocalStorage } from '$utils/get-validated-local-storage';
+import { setValidatedLocalStorage } from '$utils/set-validated-local-storage';
+
+const repositoriesListSchema = z.array(z.string());
+
+// Stores kept per repository besides the repository itself
+const repositoryStorePrefixes = [
+	'deleted_branches',
+	'locked',
+	'selected',
+	'selected-deleted',
+	'search'
+];
+
+function storageKey(prefix: string, key: string) {
+	return `store_${prefix}_${key}`;
+}
+
+function readRepository(key: string): Repository | undefined {
+	const result = getValidatedLocalStorage(
+		storageKey('repository', key),
+		RepositorySchema.optional()
+	);
+	return result.success ? result.data : undefined;
+}
+
+// Empty lists are what the stores save once cleared, they hold nothing worth keeping
+function hasSavedData(key: string) {
+	const data = getLocalStorage<unknown>(key);
+	if (data === undefined || data === null) {
+		return false;
+	}
+	if (Array.isArray(data)) {
+		return data.length > 0;
+	}
+	if (typeof data === 'object' && 'branches' in data && Array.isArray(data.branches)) {
+		return data.branches.length > 0;
+	}
+	return true;
+}
+
+// Moves the stores of a repository to another key, never overwriting saved data
+function moveRepositoryStores(fromKey: string, toKey: string) {
+	repositoryStorePrefixes.forEach((prefix) => {
+		const from = storageKey(prefix, fromKey);
+		const to = storageKey(prefix, toKey);
+		const data = localStorage.getItem(from);
+
+		if (data !== null && hasSavedData(from) && !hasSavedData(to)) {
+			localStorage.setItem(to, data);
+		}
+		localStorage.removeItem(from);
+	});
+}
+
+// A path that is gone, or no longer a repository, resolves to undefined
+async function getRepositoryRoot(path: string): Promise<GetRepositoryRootOutput | undefined> {
+	try {
+		const result = await commands.getRepositoryRoot({ path });
+		return result.status === 'ok' ? result.data : undefined;
+	} catch {
+		return undefined;
+	}
+}
+
+/**
+ * Rekeys the repositories saved under an id the backend no longer returns
+ *
+ * Repositories used to be saved under their folder name. Entries saved before the
+ * root commit was known are looked up by path and moved, with their deleted branches,
+ * locks, selection and search, to the id derived from that path. Entries whose path is
+ * gone are kept as they are.
+ *
+ * A repository added again after a move gets a new id. When exactly one other saved
+ * repository shares the root commit of a saved repository whose path is gone, that one
+ * takes over its stores and the stale entry is dropped. Clones share their root commit
+ * too, so several matches are left alone.
+ *
+ * @returns Whether the repositories list changed and must be loaded again
+ */
+export async function migrateRepositoryIds(): Promise<boolean> {
+	const listResult = getValidatedLocalStorage('repositories_list', repositoriesListSchema, []);
+	if (!listResult.success || !listResult.data) {
+		return false;
+	}
+
+	const repositories = new Map<string, Repository | undefined>();
+	let changed = false;
+
+	for (const key of listResult.data) {
+		const repository = readRepository(key);
+
+		// Only entries saved under the old ids have no root commit field at all
+		const root =
+			repository && repository.rootCommit === undefined
+				? await getRepositoryRoot(repository.path)
+				: undefined;
+
+		if (!repository || !root?.id) {
+			repositories.set(key, repository);
+			continue;
+		}
+
+		const migrated = readRepository(root.id) ?? {
+			...repository,
+			id: root.id,
+			rootCommit: root.rootCommit
+		};
+		setValidatedLocalStorage(storageKey('repository', root.id), migrated, RepositorySchema);
+
+		if (root.id !== key) {
+			localStorage.removeItem(storageKey('repository', key));
+			moveRepositoryStores(key, root.id);
+		}
+		repositories.set(root.id, migrated);
+		changed = true;
+	}
+
+	for (const [id, repository] of repositories) {
+		const rootCommit = repository?.rootCommit;
+		const candidates = [...repositories].filter(
+			([other, otherRepository]) =>
+				other !== id && !!rootCommit && otherRepository?.rootCommit === rootCommit
+		);
+
+		if (candidates.length === 0 || !repository || (await getRepositoryRoot(repository.path))) {
+			continue;
+		}
+
+		const matches: string[] = [];
+		for (const [candidate, candidateRepository] of candidates) {
+			if (candidateRepository && (await getRepositoryRoot(candidateRepository.path))) {
+				matches.push(candidate);
+			}
+		}
+		if (matches.length !== 1) {
+			continue;
+		}
+
+		moveRepositoryStores(id, matches[0]);
+		localStorage.removeItem(storageKey('repository', id));
+		repositories.delete(id);
+		changed = true;
+	}
+
+	if (changed) {
+		setValidatedLocalStorage(
+			'repositories_list',
+			[...repositories.keys()],
+			repositoriesListSchema
+		);
+	}
+
+	return changed;
+}
@@ -15,7 +15,7 @@ export class RepositoryStore extends Store<Repository | undefined> {
 		super(repository, RepositorySchema, undefined);
 	}
 
-	// Create a static reactive Set to store repository names
+	// Create a static reactive Set to store repository ids
 	static _repositoriesSet = new SvelteSet<string>();
 
 	// No static initializer block - we'll use explicit initialization instead
@@ -23,18 +23,18 @@ export class RepositoryStore extends Store<Repository | undefined> {
 	// Explicit method to load repositories that can be called when needed
 	static loadRepositories() {
 		try {
-			// Load repository names list using validated localStorage
+			// Load repository ids list using validated localStorage
 			const result = getValidatedLocalStorage('repositories_list', repositoriesListSchema, []);
 
 			if (result.success && result.data) {
 				// Clear existing data to avoid duplicates
 				RepositoryStore._repositoriesSet.clear();
 
-				// Add each repository name and ensure its store is initialized
-				result.data.forEach((name) => {
-					RepositoryStore._repositoriesSet.add(name);
+				// Add each repository id and ensure its store is initialized
+				result.data.forEach((id) => {
+					RepositoryStore._repositoriesSet.add(id);
 					// Create/get the store to ensure it's available
-					getRepositoryStore(name);
+					getRepositoryStore(id);
 				});
 			} else if (result.error) {
 				console.error('Error loading repositories list:', result.error);
@@ -95,28 +95,28 @@ export class RepositoryStore extends Store<Repository | undefined> {
 	}
 
 	set(value?: Repository) {
-		const oldName = this.state?.name; // Capture state *before* super.set
+		const oldId = this.state?.id; // Capture state *before* super.set
 
 		super.set(value);
 
-		if (value?.name) {
-			// Only navigate if the name is new or different from the old one.
+		if (value?.id) {
+			// Only navigate if the id is new or different from the old one.
 			// This prevents re-navigation when just refreshing data for the same repository.
-			if (value.name !== oldName) {
-				goto(resolve(`/repos/${value.name}`));
+			if (value.id !== oldId) {
+				goto(resolve(`/repos/${value.id}`));
 			}
-			RepositoryStore.repositories.add([value.name]);
-		} else if (oldName) {
-			// If value is undefined (repository cleared) and there was an old name,
+			RepositoryStore.repositories.add([value.id]);
+		} else if (oldId) {
+			// If value is undefined (repository cleared) and there was an old id,
 			// remove it from the set of repositories.
 			// Consider if navigation to a default page (e.g., '/') is needed here.
-			RepositoryStore.repositories.delete([oldName]);
+			RepositoryStore.repositories.delete([oldId]);
 		}
 	}
 
 	clear() {
-		if (this.state?.name) {
-			RepositoryStore.repositories.delete([this.state?.name]);
+		if (this.state?.id) {
+			RepositoryStore.repositories.delete([this.state?.id]);
 		}
 		super.clear();
 	}
@@ -135,7 +135,12 @@ vi.mock('$domains/branch-management/components/branches-bulk-actions.svelte', ()
 			// Use a valid type that matches the mutation's expected parameters
 			mockMutation.mutate({
 				path: '/mock/path',
-				branches: ['branch-1', 'branch-2']
+				branches: ['branch-1', 'branch-2'],
+				mode: null,
+				atomic: null,
+				protection: null,
+				baseBranch: null,
+				mergeDetection: null
 			});
 		});
 
@@ -171,7 +176,7 @@ describe('Repository Integration', () => {
 						{ name: 'branch-2', isRemote: false, isLocked: false }
 					],
 					error: null,
-					currentBranch: 'main',
+					currentBranch: { type: 'Branch', name: 'main' },
 					branchesCount: 3,
 					lastUpdated: new Date().toISOString()
 				});
@@ -269,13 +274,23 @@ describe('Repository Integration', () => {
 		const mockMutation = createDeleteBranchesMutation();
 		mockMutation.mutate({
 			path: '/mock/path',
-			branches: ['branch-1', 'branch-2']
+			branches: ['branch-1', 'branch-2'],
+			mode: null,
+			atomic: null,
+			protection: null,
+			baseBranch: null,
+			mergeDetection: null
 		});
 
 		// Verify delete mutation was called with correct parameters
 		expect(mockMutation.mutate).toHaveBeenCalledWith({
 			path: '/mock/path',
-			branches: ['branch-1', 'branch-2']
+			branches: ['branch-1', 'branch-2'],
+			mode: null,
+			atomic: null,
+			protection: null,
+			baseBranch: null,
+			mergeDetection: null
 		});
 	});
 });
@@ -70,30 +70,30 @@ describe('RepositoryStore', () => {
 		vi.clearAllMocks();
 	});
 
-	it('should add repository name to repositories set on set', () => {
+	it('should add repository id to repositories set on set', () => {
 		const repository = 'test-repo';
 		const store = new RepositoryStore(repository);
 		const repoData = {
 			name: 'repo-name',
 			path: '/path/to/repo',
 			branches: [],
-			currentBranch: 'main',
+			currentBranch: { type: 'Branch', name: 'main' },
 			branchesCount: 0,
 			id: 'unique-id'
 		};
 		store.set(repoData);
-		expect(goto).toHaveBeenCalledWith(`/repos/${repoData.name}`);
-		expect(mockSetStore.add).toHaveBeenCalledWith([repoData.name]);
+		expect(goto).toHaveBeenCalledWith(`/repos/${repoData.id}`);
+		expect(mockSetStore.add).toHaveBeenCalledWith([repoData.id]);
 	});
 
-	it('should remove repository name from repositories set on clear', () => {
+	it('should remove repository id from repositories set on clear', () => {
 		const repository = 'test-repo';
 		const store = new RepositoryStore(repository);
 		const repoData = {
 			name: 'repo-name',
 			path: '/path/to/repo',
 			branches: [],
-			currentBranch: 'main',
+			currentBranch: { type: 'Branch', name: 'main' },
 			branchesCount: 0,
 			id: 'unique-id'
 		};
@@ -103,7 +103,7 @@ describe('RepositoryStore', () => {
 
 		// Clear should call delete
 		store.clear();
-		expect(mockSetStore.delete).toHaveBeenCalledWith([repoData.name]);
+		expect(mockSetStore.delete).toHaveBeenCalledWith([repoData.id]);
 	});
 
 	it('should handle setting undefined and not navigate', () => {
@@ -115,7 +115,7 @@ describe('RepositoryStore', () => {
 			name: 'repo-name',
 			path: '/path/to/repo',
 			branches: [],
-			currentBranch: 'main',
+			currentBranch: { type: 'Branch', name: 'main' },
 			branchesCount: 0,
 			id: 'unique-id'
 		};
@@ -130,8 +130,8 @@ describe('RepositoryStore', () => {
 		// Should not call goto when setting undefined
 		expect(goto).not.toHaveBeenCalled();
 
-		// Should delete the previous repository name
-		expect(mockSetStore.delete).toHaveBeenCalledWith(['repo-name']);
+		// Should delete the previous repository id
+		expect(mockSetStore.delete).toHaveBeenCalledWith(['unique-id']);
 	});
 
 	it('should delete repository from repositories when setting a repo without a name', () => {
@@ -171,7 +171,7 @@ describe('RepositoryStore', () => {
 			name: 'repo-name',
 			path: '/path/to/repo',
 			branches: [],
-			currentBranch: 'main',
+			currentBranch: { type: 'Branch', name: 'main' },
 			branchesCount: 0,
 			id: 'unique-id'
 		};
@@ -185,7 +185,7 @@ describe('RepositoryStore', () => {
 		const repoWithoutName = {
 			path: '/path/to/other-repo',
 			branches: [],
-			currentBranch: 'main',
+			currentBranch: { type: 'Branch', name: 'main' },
 			branchesCount: 0,
 			id: 'unique-id-2'
 			// Intentionally missing name property
@@ -29,7 +29,7 @@ async getRepositoryRoot(input: GetRepositoryRootInput) : Promise<Result<GetRepos
  * 
  * # Arguments
  * 
- * * `input` - Input parameters containing the repository path
+ * * `input` - Input parameters containing the repository path and listing options
  * 
  * # Returns
  * 
@@ -43,6 +43,29 @@ async getRepository(input: GetRepositoryInput) : Promise<Result<GetRepositoryOut
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Lists the git repositories found in a workspace folder.
+ * 
+ * A `RepositoryDiscoveredEvent` is emitted for each repository as soon as it
+ * is found, so results can be shown before the scan completes.
+ * 
+ * # Arguments
+ * 
+ * * `app` - The AppHandle
+ * * `input` - Input parameters containing the workspace path and scan options
+ * 
+ * # Returns
+ * 
+ * * `Result<ListRepositoriesOutput, AppError>` - Every repository found or an error
+ */
+async listRepositories(input: ListRepositoriesInput) : Promise<Result<ListRepositoriesOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_repositories", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Switches to another branch in a git repository.
  * 
@@ -62,16 +85,58 @@ async switchBranch(input: SwitchBranchInput) : Promise<Result<SwitchBranchOutput
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Locks a branch in the repository's local config, protecting it from deletion.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing path and branch name
+ * 
+ * # Returns
+ * 
+ * * `Result<LockBranchOutput, AppError>` - The locked branch or an error
+ */
+async lockBranch(input: LockBranchInput) : Promise<Result<LockBranchOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lock_branch", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Removes the lock of a branch from the repository's local config.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing path and branch name
+ * 
+ * # Returns
+ * 
+ * * `Result<LockBranchOutput, AppError>` - The unlocked branch or an error
+ */
+async unlockBranch(input: LockBranchInput) : Promise<Result<LockBranchOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_branch", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Deletes branches from a git repository.
  * 
+ * A branch that cannot be deleted is reported in its result and the others
+ * are still deleted, unless the deletion is atomic: then nothing is deleted
+ * and the first failure is returned.
+ * 
  * # Arguments
  * 
- * * `input` - Input parameters containing path and branch names to delete
+ * * `input` - Input parameters containing path, branch names to delete, delete mode, atomicity, protection rules and base branch
  * 
  * # Returns
  * 
- * * `Result<DeleteBranchesOutput, AppError>` - The deleted branches or an error
+ * * `Result<DeleteBranchesOutput, AppError>` - The outcome of each branch or an error
  */
 async deleteBranches(input: DeleteBranchesInput) : Promise<Result<DeleteBranchesOutput, AppError>> {
     try {
@@ -81,6 +146,46 @@ async deleteBranches(input: DeleteBranchesInput) : Promise<Result<DeleteBranches
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Previews the deletion of branches without touching any reference.
+ * 
+ * # Arguments
+ * 
+ * * `input` - The same input as `delete_branches`
+ * 
+ * # Returns
+ * 
+ * * `Result<PlanDeleteBranchesOutput, AppError>` - Whether each branch would be deleted, and the commits it would leave unreachable
+ */
+async planDeleteBranches(input: DeleteBranchesInput) : Promise<Result<PlanDeleteBranchesOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plan_delete_branches", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes the branches selected by one cleanup plan in several repositories.
+ * 
+ * A repository that fails is reported in its result, the others are still cleaned.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing the repository paths and the cleanup plan
+ * 
+ * # Returns
+ * 
+ * * `Result<DeleteBranchesInRepositoriesOutput, AppError>` - The result of each repository
+ */
+async deleteBranchesInRepositories(input: DeleteBranchesInRepositoriesInput) : Promise<Result<DeleteBranchesInRepositoriesOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_branches_in_repositories", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Checks if a commit SHA is reachable in a git repository.
  * 
@@ -100,6 +205,143 @@ async isCommitReachable(input: IsCommitReachableInput) : Promise<Result<IsCommit
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Gets the full metadata of a commit: message body, committer, parents and trailers.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing path and commit SHA
+ * 
+ * # Returns
+ * 
+ * * `Result<GetCommitDetailsOutput, AppError>` - The commit details or an error
+ */
+async getCommitDetails(input: GetCommitDetailsInput) : Promise<Result<GetCommitDetailsOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_commit_details", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists the remote-tracking branches of a git repository.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing the repository path and listing options
+ * 
+ * # Returns
+ * 
+ * * `Result<ListRemoteBranchesOutput, AppError>` - The remote-tracking branches or an error
+ */
+async listRemoteBranches(input: ListRemoteBranchesInput) : Promise<Result<ListRemoteBranchesOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_remote_branches", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists the local branches whose upstream no longer exists.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing the repository path and listing options
+ * 
+ * # Returns
+ * 
+ * * `Result<ListGoneBranchesOutput, AppError>` - The branches with a gone upstream or an error
+ */
+async listGoneBranches(input: ListGoneBranchesInput) : Promise<Result<ListGoneBranchesOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_gone_branches", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists the files changed, insertions and deletions of branches since their
+ * merge-base with the base branch.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing the repository path, branch names and base branch
+ * 
+ * # Returns
+ * 
+ * * `Result<ListBranchDiffStatsOutput, AppError>` - The diff statistics per branch or an error
+ */
+async listBranchDiffStats(input: ListBranchDiffStatsInput) : Promise<Result<ListBranchDiffStatsOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_branch_diff_stats", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists the linked worktrees of a git repository.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing the repository path
+ * 
+ * # Returns
+ * 
+ * * `Result<ListWorktreesOutput, AppError>` - The worktrees or an error
+ */
+async listWorktrees(input: ListWorktreesInput) : Promise<Result<ListWorktreesOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_worktrees", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes a linked worktree, its working directory included.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing path, worktree name and force flag
+ * 
+ * # Returns
+ * 
+ * * `Result<DeleteWorktreeOutput, AppError>` - The deleted worktree or an error
+ */
+async deleteWorktree(input: DeleteWorktreeInput) : Promise<Result<DeleteWorktreeOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_worktree", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Prunes the metadata of worktrees whose directory no longer exists.
+ * 
+ * A worktree that fails to prune does not stop the others, its error is
+ * reported in `results`.
+ * 
+ * # Arguments
+ * 
+ * * `input` - Input parameters containing the repository path
+ * 
+ * # Returns
+ * 
+ * * `Result<PruneWorktreesOutput, AppError>` - The pruned worktrees and the outcome for each, or an error
+ */
+async pruneWorktrees(input: PruneWorktreesInput) : Promise<Result<PruneWorktreesOutput, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("prune_worktrees", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Restores a deleted branch in a git repository.
  * 
@@ -150,12 +392,14 @@ branchDeleted: BranchDeletedEvent,
 branchRestored: BranchRestoredEvent,
 branchSwitched: BranchSwitchedEvent,
 notification: NotificationEvent,
+repositoryDiscovered: RepositoryDiscoveredEvent,
 repositoryLoaded: RepositoryLoadedEvent
 }>({
 branchDeleted: "branch-deleted",
 branchRestored: "branch-restored",
 branchSwitched: "branch-switched",
 notification: "notification",
+repositoryDiscovered: "repository-discovered",
 repositoryLoaded: "repository-loaded"
 })
 
@@ -166,33 +410,360 @@ repositoryLoaded: "repository-loaded"
 /** user-defined types **/
 
 export type AppError = { message: string; kind: string; description: string | null }
-export type Branch = { name: string; fullyMerged: boolean; lastCommit: Commit; current: boolean }
+/**
+ * Why a branch cannot be deleted.
+ */
+export type BlockedReason = "NotFound" | "Current" | "Worktree" | "Protected" | "NotMerged"
+export type Branch = { name: string; fullyMerged: boolean; lastCommit: Commit; current: boolean; /**
+ * Remote of a remote-tracking branch, `None` for local branches
+ */
+remote: string | null; /**
+ * Upstream the branch tracks, e.g. `origin/main`
+ */
+upstream: string | null; /**
+ * Commits not yet pushed to the upstream
+ */
+ahead: number | null; /**
+ * Commits on the upstream not yet merged locally
+ */
+behind: number | null; /**
+ * Whether the configured upstream no longer exists
+ */
+upstreamGone: boolean; /**
+ * How the branch was merged into the base branch
+ */
+mergeKind: MergeKind; /**
+ * How many of the branch's own commits already have an equivalent in the
+ * base branch, only computed with content merge detection
+ */
+commitsInBase: CommitsInBase | null; /**
+ * Commits not reachable from the base branch, lost if the branch is deleted
+ */
+uniqueCommits: number; /**
+ * SHA of the best common ancestor with the base branch
+ */
+mergeBase: string | null; /**
+ * When the branch was created, `None` if its reflog is missing or expired
+ */
+createdAt: CommitTime | null; /**
+ * When the branch was last checked out according to the HEAD reflog
+ */
+lastCheckedOutAt: CommitTime | null; /**
+ * Path of the other worktree the branch is checked out in, if any
+ */
+worktreePath: string | null; /**
+ * Whether the branch is locked in the repository's local config
+ */
+locked: boolean; /**
+ * Whether protection rules keep the branch from being deleted, set by
+ * the branch management services
+ */
+protected: boolean }
 export type BranchDeletedEvent = { deletedBranches: DeletedBranchInfo[]; repositoryPath: string }
+/**
+ * The planned deletion of one branch.
+ */
+export type BranchDeletionPlan = { branch: string; action: DeletionAction; /**
+ * Why the branch is kept, `None` when it would be deleted
+ */
+blockedReason: BlockedReason | null; /**
+ * Worktree the branch is checked out in, if any
+ */
+worktreePath: string | null; /**
+ * Commits no other reference reaches once the planned branches are deleted
+ */
+unreachableCommits: number }
+/**
+ * Outcome of deleting one branch.
+ */
+export type BranchDeletionResult = { branch: string; /**
+ * The branch as it was before its deletion, `None` when it was not deleted
+ */
+deleted: DeletedBranchInfo | null; /**
+ * Why the branch was not deleted
+ */
+error: AppError | null }
+/**
+ * Size of a branch: the changes between its merge-base with the base branch
+ * and its tip, like `git diff --shortstat base...branch`.
+ */
+export type BranchDiffStats = { branch: string; filesChanged: number; insertions: number; deletions: number }
 export type BranchRestoredEvent = { restoredBranch: Branch; repositoryPath: string }
 export type BranchSwitchedEvent = { fromBranch: string; toBranch: string; repositoryPath: string }
-export type Commit = { sha: string; shortSha: string; date: string; message: string; author: string; email: string }
+/**
+ * A cleanup applied the same way to every repository.
+ * 
+ * Protected branches, the base branch and branches checked out in another
+ * worktree are never deleted. Each repository keeps the branches it could
+ * delete unless the plan is atomic.
+ */
+export type CleanupPlan = { target: CleanupTarget; /**
+ * Glob patterns of branches to keep, e.g. `release/*`
+ */
+excludePatterns: string[]; /**
+ * Branch to measure merged status against, auto-detected per repository when `None`
+ */
+baseBranch: string | null; /**
+ * Merge detection mode, `Ancestry` when `None`
+ */
+mergeDetection: MergeDetection | null; /**
+ * Branches to keep, only the current and default branch when `None`
+ */
+protection: ProtectionRules | null; /**
+ * Delete all selected branches of a repository or none of them, `false` when `None`
+ */
+atomic: boolean | null }
+/**
+ * Which branches a cleanup plan deletes.
+ */
+export type CleanupTarget = "Merged" | "Gone" | "MergedOrGone"
+export type Commit = { sha: string; shortSha: string; /**
+ * Committer date formatted as `%a %b %e %T %Y %z`
+ */
+date: string; message: string; author: string; email: string; /**
+ * When the author originally wrote the commit
+ */
+authoredAt: CommitTime; /**
+ * When the commit was last applied, e.g. by a rebase or cherry-pick
+ */
+committedAt: CommitTime }
+/**
+ * Everything about a commit, beyond the summary shown in branch listings.
+ */
+export type CommitDetails = { sha: string; shortSha: string; /**
+ * First paragraph of the message
+ */
+summary: string; /**
+ * Message after the summary, trailers included, `None` if there is none
+ */
+body: string | null; author: string; email: string; authoredAt: CommitTime; committer: string; committerEmail: string; committedAt: CommitTime; /**
+ * More than one parent means a merge commit
+ */
+parentCount: number; trailers: CommitTrailer[] }
+/**
+ * A timestamp as git records it: when, and in which timezone.
+ */
+export type CommitTime = { /**
+ * Seconds since the Unix epoch
+ */
+seconds: number; /**
+ * Offset from UTC in minutes, e.g. `120` for `+0200`
+ */
+offsetMinutes: number }
+/**
+ * A `Key: value` line from the end of a commit message, e.g. `Signed-off-by`.
+ */
+export type CommitTrailer = { key: string; value: string }
+/**
+ * How many of a branch's own commits already have an equivalent commit, one
+ * with the same patch-id, in the base branch. Mirrors `git cherry`.
+ */
+export type CommitsInBase = { /**
+ * Branch commits whose patch is already in the base branch
+ */
+count: number; /**
+ * Non-merge commits on the branch that are not reachable from the base
+ */
+total: number }
 export type ConflictDetails = { originalName: string; conflictingName: string }
 export type ConflictResolution = "Overwrite" | "Rename" | "Skip"
-export type DeleteBranchesInput = { path: string; branches: string[] }
-export type DeleteBranchesOutput = { deletedBranches: DeletedBranchInfo[] }
+/**
+ * What HEAD points to.
+ */
+export type CurrentBranch = { type: "Branch"; name: string } | { type: "Detached"; sha: string } | { type: "Unborn"; name: string }
+export type DeleteBranchesInRepositoriesInput = { paths: string[]; plan: CleanupPlan }
+export type DeleteBranchesInRepositoriesOutput = { results: RepositoryCleanupResult[] }
+export type DeleteBranchesInput = { path: string; branches: string[]; /**
+ * Whether unmerged branches are refused, `Force` when `None`
+ */
+mode: DeleteMode | null; /**
+ * Delete all branches or none of them, `false` when `None`
+ */
+atomic: boolean | null; /**
+ * Branches to keep, only the current and default branch when `None`
+ */
+protection: ProtectionRules | null; /**
+ * Base branch `Safe` mode measures merged status against, auto-detected when `None`
+ */
+baseBranch: string | null; /**
+ * Merge detection mode the listing used, `Ancestry` when `None`. `Safe` mode
+ * still refuses branches that are only squashed or rebased into the base
+ * branch
+ */
+mergeDetection: MergeDetection | null }
+export type DeleteBranchesOutput = { deletedBranches: DeletedBranchInfo[]; /**
+ * Outcome of each requested branch, including the ones left in place
+ */
+results: BranchDeletionResult[] }
+/**
+ * How `delete_branches` treats branches that are not merged.
+ */
+export type DeleteMode = "Safe" | "Force"
+export type DeleteWorktreeInput = { path: string; /**
+ * Name of the worktree, as returned by `list_worktrees`
+ */
+name: string; /**
+ * Also remove locked worktrees and worktrees with uncommitted changes
+ */
+force: boolean | null }
+export type DeleteWorktreeOutput = { deletedWorktree: WorktreeInfo }
 export type DeletedBranch = { originalName: string; targetName: string; commitSha: string; conflictResolution: ConflictResolution | null }
 export type DeletedBranchInfo = { branch: Branch; rawOutput: string }
-export type GetRepositoryInput = { path: string }
-export type GetRepositoryOutput = { path: string; branches: Branch[]; currentBranch: string; branchesCount: number; name: string; id: string }
+/**
+ * What deleting a branch would do.
+ */
+export type DeletionAction = "Delete" | "Blocked"
+export type GetCommitDetailsInput = { path: string; commitSha: string }
+export type GetCommitDetailsOutput = { commit: CommitDetails }
+export type GetRepositoryInput = { path: string; /**
+ * Branch to measure merged status against, auto-detected when `None`
+ */
+baseBranch: string | null; /**
+ * Merge detection mode, `Ancestry` when `None`
+ */
+mergeDetection: MergeDetection | null }
+export type GetRepositoryOutput = { path: string; branches: Branch[]; /**
+ * What HEAD points to: a branch, a detached commit or an unborn branch
+ */
+currentBranch: CurrentBranch; branchesCount: number; name: string; id: string; rootCommit: string | null; baseBranch: string | null; baseCandidates: string[]; /**
+ * Why `branches` may be empty
+ */
+status: RepositoryStatus; /**
+ * Whether the repository is bare, in which case `switch_branch` is unavailable
+ */
+isBare: boolean }
 export type GetRepositoryRootInput = { path: string }
-export type GetRepositoryRootOutput = { rootPath: string; id: string | null }
+export type GetRepositoryRootOutput = { rootPath: string; /**
+ * Stable id derived from the canonical root path, shared with `get_repository`
+ */
+id: string | null; /**
+ * First commit of the history, to recognise the repository after a move
+ */
+rootCommit: string | null }
 export type IsCommitReachableInput = { path: string; commitSha: string }
 export type IsCommitReachableOutput = { isReachable: boolean }
+export type ListBranchDiffStatsInput = { path: string; branches: string[]; baseBranch: string | null }
+export type ListBranchDiffStatsOutput = { stats: BranchDiffStats[] }
+export type ListGoneBranchesInput = { path: string; baseBranch: string | null; mergeDetection: MergeDetection | null }
+export type ListGoneBranchesOutput = { branches: Branch[] }
+export type ListRemoteBranchesInput = { path: string; baseBranch: string | null; mergeDetection: MergeDetection | null }
+export type ListRemoteBranchesOutput = { branches: Branch[] }
+export type ListRepositoriesInput = { /**
+ * Workspace folder to scan
+ */
+path: string; /**
+ * How many folder levels below `path` are visited, 4 when `None`
+ */
+maxDepth: number | null; /**
+ * Glob patterns of folders to skip, `node_modules` is always skipped
+ */
+ignorePatterns: string[] | null; /**
+ * Branch to count merged branches against, auto-detected per repository when `None`
+ */
+baseBranch: string | null }
+export type ListRepositoriesOutput = { repositories: ScannedRepository[] }
+export type ListWorktreesInput = { path: string }
+export type ListWorktreesOutput = { worktrees: WorktreeInfo[] }
+export type LockBranchInput = { path: string; branch: string }
+export type LockBranchOutput = { branch: string; locked: boolean }
+/**
+ * How much work is spent deciding whether a branch is merged.
+ */
+export type MergeDetection = "Ancestry" | "Content"
+/**
+ * How a branch ended up in the base branch.
+ */
+export type MergeKind = "Ancestor" | "Squashed" | "Rebased" | "None"
 export type NotificationEvent = { title: string; message: string; kind: NotificationKind; duration: number | null }
 export type NotificationKind = "Success" | "Error" | "Warning" | "Info"
+export type PlanDeleteBranchesOutput = { branches: BranchDeletionPlan[] }
+/**
+ * Branches the backend refuses to delete, whichever client asks.
+ */
+export type ProtectionRules = { /**
+ * Protected branch names, e.g. `main`
+ */
+names: string[]; /**
+ * Glob patterns of protected branches, e.g. `release/*`
+ */
+patterns: string[]; /**
+ * Whether the checked out branch is protected
+ */
+currentBranch: boolean; /**
+ * Whether the default branch of the repository is protected
+ */
+defaultBranch: boolean; /**
+ * Whether branches locked in the repository's config are protected
+ */
+lockedBranches: boolean }
+export type PruneWorktreesInput = { path: string }
+export type PruneWorktreesOutput = { prunedWorktrees: WorktreeInfo[]; /**
+ * Outcome for each prunable worktree, failures included
+ */
+results: WorktreePruneResult[] }
+/**
+ * Outcome of a cleanup in one repository.
+ */
+export type RepositoryCleanupResult = { path: string; deletedBranches: DeletedBranchInfo[]; /**
+ * Outcome of each selected branch, including the ones left in place
+ */
+results: BranchDeletionResult[]; /**
+ * Why the repository could not be cleaned, nothing was deleted in it then
+ */
+error: AppError | null }
+/**
+ * Emitted by `list_repositories` for each repository as soon as it is found.
+ */
+export type RepositoryDiscoveredEvent = { workspacePath: string; repository: ScannedRepository }
 export type RepositoryLoadedEvent = { repositoryPath: string; repositoryName: string; branchesCount: number }
+/**
+ * Whether a repository has anything to list, so an empty branch list can be
+ * told apart from a failure.
+ */
+export type RepositoryStatus = "Ready" | "NoCommits" | "NoBranches"
 export type RestoreBranchInput = { path: string; branchInfo: DeletedBranch }
 export type RestoreBranchOutput = { result: RestoreBranchResult }
 export type RestoreBranchResult = { success: boolean; branchName: string; message: string; requiresUserAction: boolean; conflictDetails: ConflictDetails | null; skipped: boolean; branch: Branch | null }
 export type RestoreBranchesInput = { path: string; branchInfos: DeletedBranch[] }
 export type RestoreBranchesOutput = { results: RestoreBranchResult[] }
+/**
+ * A repository found while scanning a workspace.
+ */
+export type ScannedRepository = { path: string; name: string; /**
+ * Same stable id as `get_repository`
+ */
+id: string; isBare: boolean; branchesCount: number; /**
+ * Branches, other than the current one, merged into the base branch
+ */
+mergedBranchesCount: number; /**
+ * Why the branches could not be listed, the counts are zero then
+ */
+error: AppError | null }
 export type SwitchBranchInput = { path: string; branch: string }
 export type SwitchBranchOutput = { currentBranch: string }
+/**
+ * A linked worktree, as listed by `git worktree list`.
+ */
+export type WorktreeInfo = { /**
+ * Name of the worktree metadata in `.git/worktrees`
+ */
+name: string; path: string; /**
+ * Branch checked out in the worktree, `None` when detached or unreadable
+ */
+branch: string | null; locked: boolean; lockReason: string | null; /**
+ * Whether the working directory is gone and only stale metadata remains
+ */
+prunable: boolean }
+/**
+ * Outcome of pruning one worktree.
+ */
+export type WorktreePruneResult = { name: string; /**
+ * The worktree as it was before pruning, `None` when it was not pruned
+ */
+pruned: WorktreeInfo | null; /**
+ * Why the worktree was not pruned
+ */
+error: AppError | null }
 
 /** tauri-specta globals **/
 
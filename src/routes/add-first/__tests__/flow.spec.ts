@@ -175,7 +175,7 @@ describe('Add Repository Flow Integration', () => {
 			path: '/mock/repo/path',
 			branches: [],
 			branchesCount: 0,
-			currentBranch: 'main'
+			currentBranch: { type: 'Branch', name: 'main' }
 		};
 
 		// Make sure RepositoryStore.repositories.has returns true to simulate an existing repo
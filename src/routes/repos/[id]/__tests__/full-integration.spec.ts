@@ -108,7 +108,7 @@ vi.mock('$domains/repository-management/services/create-get-repository-query', (
 						fullyMerged: false
 					}
 				],
-				currentBranch: 'main',
+				currentBranch: { type: 'Branch', name: 'main' },
 				branchesCount: 3
 			},
 			isLoading: false,
@@ -146,7 +146,7 @@ vi.mock('$domains/repository-management/services/create-get-repository-query', (
 							fullyMerged: false
 						}
 					],
-					currentBranch: 'main',
+					currentBranch: { type: 'Branch', name: 'main' },
 					branchesCount: 2
 				}
 			}),
@@ -243,7 +243,7 @@ vi.mock('$domains/branch-management/services/createDeleteBranchesMutation', () =
 										fullyMerged: false
 									}
 								],
-								currentBranch: 'main',
+								currentBranch: { type: 'Branch', name: 'main' },
 								branchesCount: 2
 							});
 						}
@@ -434,13 +434,13 @@ describe('Repository Page Integration Test', () => {
 
 		// Only branches with "feature" should be visible
 		// The full branches list should be filtered
-		const searchStore = getSearchBranchesStore('test-repo');
+		const searchStore = getSearchBranchesStore('test-repo-id');
 		expect(searchStore?.state).toBe('feature');
 
 		// Verify that only one branch is rendered
 		const branchList = screen.getByRole('list');
 		const branchCheckboxes = within(branchList).getAllByRole('checkbox');
-		expect(branchCheckboxes.length).toBe(2);
+		expect(branchCheckboxes.length).toBe(1);
 	});
 
 	it('switches the current branch', async () => {
@@ -26,14 +26,34 @@ export const BranchSchema = z
 
 export type Branch = z.infer<typeof BranchSchema>;
 
+// What HEAD points to, tagged like the backend's CurrentBranch
+export const CurrentBranchSchema = z.discriminatedUnion('type', [
+	z.object({ type: z.literal('Branch'), name: z.string() }),
+	z.object({ type: z.literal('Detached'), sha: z.string() }),
+	z.object({ type: z.literal('Unborn'), name: z.string() })
+]);
+
+export type CurrentBranch = z.infer<typeof CurrentBranchSchema>;
+
+/**
+ * Gets the name of the checked out branch
+ * @param currentBranch - What HEAD points to
+ * @returns The branch name, even when it has no commit yet, or undefined when HEAD is detached or unknown
+ */
+export function getCurrentBranchName(currentBranch?: CurrentBranch): string | undefined {
+	return currentBranch?.type === 'Detached' ? undefined : currentBranch?.name;
+}
+
 // Repository schema
 export const RepositorySchema = z.object({
 	branches: z.array(BranchSchema),
 	path: z.string(),
 	name: z.string(),
-	currentBranch: z.string(),
+	currentBranch: CurrentBranchSchema,
 	branchesCount: z.number(),
-	id: z.string()
+	id: z.string(),
+	// Missing on repositories saved before ids were derived from the path
+	rootCommit: z.string().nullable().optional()
 });
 
 export type Repository = z.infer<typeof RepositorySchema>;
@@ -35,7 +35,7 @@ export const mockDataFactory = {
 		path: '/test/repo',
 		name: 'test-repo',
 		id: 'test-repo-id',
-		currentBranch: 'main',
+		currentBranch: { type: 'Branch', name: 'main' },
 		branchesCount: 2,
 		branches: [
 			mockDataFactory.branch({ name: 'main', current: true }),